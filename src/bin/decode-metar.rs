@@ -44,14 +44,14 @@ impl FromStr for MetarFileFormat {
 }
 
 /// Decode METAR reports in a file with NOAA METAR cycle format.
-fn decode_noaa_metar_cycles_file(path: &Path) -> Result<Vec<metar::Metar>> {
+fn decode_noaa_metar_cycles_file(path: &Path, options: metar::DecodeOptions) -> Result<Vec<metar::Metar>> {
     let file = File::open(path)?;
     let enc_reader = DecodeReaderBytesBuilder::new()
         .encoding(Some(WINDOWS_1252))
         .build(file);
     let buf_reader = BufReader::new(enc_reader);
 
-    let mut obs_time_opt = None;
+    let mut decoder_opt = None;
     let mut all_metar_data = Vec::new();
 
     for row in buf_reader.lines() {
@@ -63,9 +63,9 @@ fn decode_noaa_metar_cycles_file(path: &Path) -> Result<Vec<metar::Metar>> {
         }
 
         if let Ok(obs_time) = NaiveDateTime::parse_from_str(row, "%Y/%m/%d %H:%M") {
-            obs_time_opt = Some(obs_time);
-        } else if obs_time_opt.is_some() {
-            match metar::decode_metar(row, obs_time_opt) {
+            decoder_opt = Some(metar::Decoder::new(options.anchor_time(obs_time)));
+        } else if let Some(decoder) = &decoder_opt {
+            match decoder.decode(row) {
                 Ok(metar_data) => all_metar_data.push(metar_data),
                 Err(e) => log::warn!("{:#}", e),
             }
@@ -76,10 +76,11 @@ fn decode_noaa_metar_cycles_file(path: &Path) -> Result<Vec<metar::Metar>> {
 }
 
 /// Decode METAR reports in a file with plain format.
-fn decode_plain_file(path: &Path, anchor_time: Option<NaiveDateTime>) -> Result<Vec<metar::Metar>> {
+fn decode_plain_file(path: &Path, options: metar::DecodeOptions) -> Result<Vec<metar::Metar>> {
     let file = File::open(path)?;
     let buf_reader = BufReader::new(file);
 
+    let decoder = metar::Decoder::new(options);
     let mut all_metar_data = Vec::new();
 
     for row in buf_reader.lines() {
@@ -90,7 +91,7 @@ fn decode_plain_file(path: &Path, anchor_time: Option<NaiveDateTime>) -> Result<
             continue;
         }
 
-        match metar::decode_metar(&row, anchor_time) {
+        match decoder.decode(row) {
             Ok(metar_data) => all_metar_data.push(metar_data),
             Err(e) => log::warn!("{:#}", e),
         }
@@ -120,6 +121,26 @@ struct Cli {
     /// If given, the decoded METAR day and time will be converted to a full datetime.
     #[structopt(short, long, parse(try_from_str = naive_date_time_from_yyyy_mm_dd_str))]
     anchor_time: Option<NaiveDateTime>,
+    /// Policy of converting the METAR day and time into a full datetime (reject, clamp-to-month-end, past-only).
+    /// Use past-only for archives where the anchor time is the time of publication.
+    #[structopt(long, default_value = "reject")]
+    anchor_policy: metar::AnchorPolicy,
+    /// Unit system of the decoded values (metric, united-states). If not given, the values are kept in the reported units.
+    #[structopt(short, long)]
+    units: Option<metar::units::UnitSystem>,
+    /// Region of the national remark groups (united-states, canada, russia, japan).
+    /// If not given, the region is selected by the station of each report.
+    #[structopt(long)]
+    region: Option<metar::remark::RemarkRegion>,
+    /// Keep the remark groups only in the raw remark text without decoding them
+    #[structopt(long)]
+    no_remarks: bool,
+    /// Record the source span of every decoded group in the provenance
+    #[structopt(long)]
+    provenance: bool,
+    /// List the violations of WMO FM 15 in every decoded report
+    #[structopt(long)]
+    strict: bool,
     /// Input files (glob patterns separated by space)
     #[structopt(required = true)]
     input_globs: Vec<String>,
@@ -146,13 +167,34 @@ fn main() -> Result<()> {
 
     log::info!("Found {} file(s)", input_paths.len());
 
+    let mut options = metar::DecodeOptions::new()
+        .anchor_policy(args.anchor_policy)
+        .remarks(!args.no_remarks)
+        .provenance(args.provenance)
+        .strict(args.strict);
+
+    if let Some(region) = args.region {
+        options = options.region(region);
+    }
+
+    if let Some(units) = args.units {
+        options = options.units(units);
+    }
+
     let mut unique_reports = HashSet::new();
     let mut all_metars = Vec::new();
 
     for input_path in input_paths.iter() {
         let metars = match args.file_format {
-            MetarFileFormat::NoaaMetarCycles => decode_noaa_metar_cycles_file(input_path)?,
-            MetarFileFormat::Plain => decode_plain_file(input_path, args.anchor_time)?,
+            MetarFileFormat::NoaaMetarCycles => decode_noaa_metar_cycles_file(input_path, options)?,
+            MetarFileFormat::Plain => {
+                let options = match args.anchor_time {
+                    Some(at) => options.anchor_time(at),
+                    None => options,
+                };
+
+                decode_plain_file(input_path, options)?
+            },
         };
 
         for metar in metars.into_iter() {
@@ -2,3 +2,5 @@
 
 pub mod datetime;
 pub mod metar;
+pub mod number;
+pub mod report;
@@ -5,16 +5,26 @@
 //! - World Meteorological Organization (2019). Manual on Codes, Volume I.1 – International Codes. Available: <https://library.wmo.int/idurl/4/35713>.
 //! - World Meteorological Organization (2018). Manual on Codes, Volume II – Regional Codes and National Coding Practices. Available: <https://library.wmo.int/idurl/4/35717>.
 
-use std::{ops::{Div, Mul}, str::FromStr};
+use std::{collections::BTreeMap, fmt, ops::{Div, Mul, Range}, str::FromStr, sync::Arc};
 
 use anyhow::{anyhow, Error, Result};
 use chrono::{NaiveDateTime, NaiveTime, Datelike, Duration};
 use chronoutil::RelativeDuration;
 use lazy_static::lazy_static;
-use regex::Regex;
+use regex::{Captures, Regex};
 use serde::{Serialize, Deserialize};
 
-use crate::datetime::{UtcDateTime, UtcDayTime, UtcTime};
+use crate::{datetime::{UtcDateTime, UtcDayTime, UtcTime}, number::Number};
+
+use self::{conformance::Violation, diagnostics::{Diagnostics, GroupKind, WarningKind}, error::{DecodeError, InvalidGroup}, extension::{GroupHandler, GroupSection, HandlerPlacement, RegisteredHandler}, provenance::Provenance, remark::{DynRemarkDecoder, RemarkDecoder, RemarkRegion, Remarks, SensorStatus}, units::UnitSystem};
+
+pub mod conformance;
+pub mod diagnostics;
+pub mod error;
+pub mod extension;
+pub mod provenance;
+pub mod remark;
+pub mod units;
 
 lazy_static! {
     static ref WHITESPACE_REPLACE_RE: Regex = Regex::new(r"\s+").unwrap();
@@ -50,7 +60,7 @@ lazy_static! {
 
     static ref VISIBILITY_RE: Regex = Regex::new(r"(?x)
         ^(?P<prevailing>[MP]?(\d+\s)?\d/\d{1,2}|[MP]?\d{1,5}|////|[CK]AVOK)
-        (NDV)?
+        (?P<ndv>NDV)?
         \s?
         (?P<units>SM|KM)?
         (\s(?P<minimum>[MP]?\d{1,4}))?
@@ -108,7 +118,17 @@ lazy_static! {
     static ref WIND_SHEAR_RE: Regex = Regex::new(r"(?x)
         ^WS
         \s
-        (?P<runway>R\d\d[A-Z]?|ALL\sRWY)
+        ((?P<phase>TKOF|LDG)\s)?
+        (?P<runway>R(WY)?\d\d[A-Z]?|ALL\sRWYS?)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref LOW_LEVEL_WIND_SHEAR_RE: Regex = Regex::new(r"(?x)
+        ^WS(?P<height>\d\d\d)
+        /
+        (?P<direction>\d\d\d)
+        (?P<speed>\d\d\d?)
+        (?P<units>KT|MPS)
         (?P<end>\s)
     ").unwrap();
 
@@ -231,52 +251,90 @@ impl MetarTime {
     /// Converts any [MetarTime] into [MetarTime::DateTime].
     ///
     /// Using `anchor_time`, any [MetarTime] will be converted to a [MetarTime::DateTime] that is nearest
-    /// to the specified `anchor_time` while preserving all the datetime information in the input [MetarTime].
+    /// to the specified `anchor_time` while preserving all the datetime information in the input [MetarTime],
+    /// or to the latest one not after the `anchor_time` with [AnchorPolicy::PastOnly].
     /// This conversion correctly handles months with different number of days and also leap years.
-    pub fn to_date_time(&self, anchor_time: NaiveDateTime) -> MetarTime {
-        match self {
-            MetarTime::DateTime(utc_dt) => MetarTime::DateTime(*utc_dt),
+    ///
+    /// Returns [None] if no nearby month contains the day, e.g. day 31 near February or day 0,
+    /// unless the day is clamped with [AnchorPolicy::ClampToMonthEnd].
+    pub fn to_date_time(&self, anchor_time: NaiveDateTime, policy: AnchorPolicy) -> Option<MetarTime> {
+        let guesses = match self {
+            MetarTime::DateTime(utc_dt) => return Some(MetarTime::DateTime(*utc_dt)),
             MetarTime::DayTime(utc_d_t) => {
-                let first_guess_opt = anchor_time.date().with_day(utc_d_t.0).map(|nd| nd.and_time(utc_d_t.1));
-                let second_guess_opt = (anchor_time + RelativeDuration::months(-1)).date().with_day(utc_d_t.0).map(|nd| nd.and_time(utc_d_t.1));
-                let third_guess_opt = (anchor_time + RelativeDuration::months(1)).date().with_day(utc_d_t.0).map(|nd| nd.and_time(utc_d_t.1));
-
-                let mut final_guess_opt = None;
-                let mut final_delta = i64::MAX;
-
-                for guess_opt in [first_guess_opt, second_guess_opt, third_guess_opt] {
-                    if let Some(guess) = guess_opt {
-                        let delta = guess.signed_duration_since(anchor_time).num_seconds().abs();
-                        if delta < final_delta {
-                            final_guess_opt = guess_opt;
-                            final_delta = delta;
-                        }
-                    }
+                // two months back are needed when the past month does not contain the day, e.g. day 31 in March
+                let months: &[i32] = match policy {
+                    AnchorPolicy::PastOnly => &[0, -1, -2],
+                    _ => &[0, -1, 1],
+                };
+
+                let dates = months.iter()
+                    .map(|&m| (anchor_time + RelativeDuration::months(m)).date());
+
+                let mut guesses = dates.clone()
+                    .filter_map(|date| date.with_day(utc_d_t.0))
+                    .map(|nd| nd.and_time(utc_d_t.1))
+                    .collect::<Vec<_>>();
+
+                // the day is clamped only if no nearby month contains it
+                if guesses.is_empty() && policy == AnchorPolicy::ClampToMonthEnd {
+                    guesses = dates
+                        .filter_map(|date| {
+                            let last_day = (28..=31).rev().find(|&d| date.with_day(d).is_some()).unwrap();
+                            date.with_day(utc_d_t.0.min(last_day))
+                        })
+                        .map(|nd| nd.and_time(utc_d_t.1))
+                        .collect();
                 }
 
-                match final_guess_opt {
-                    Some(final_guess) => MetarTime::DateTime(UtcDateTime(final_guess)),
-                    None => panic!("{}", format!("Date guessing failed, given time {:?} and anchor time {}", self, anchor_time))
-                }
+                guesses
             },
             MetarTime::Time(utc_t) => {
                 let first_guess = anchor_time.date().and_time(utc_t.0);
-                let second_guess = first_guess + Duration::days(-1);
-                let third_guess = first_guess + Duration::days(1);
 
-                let mut final_guess = first_guess;
-                let mut final_delta = final_guess.signed_duration_since(anchor_time).num_seconds().abs();
+                [0, -1, 1].iter()
+                    .map(|&d| first_guess + Duration::days(d))
+                    .collect::<Vec<_>>()
+            },
+        };
+
+        let final_guess_opt = match policy {
+            AnchorPolicy::PastOnly => guesses.into_iter()
+                .filter(|&guess| guess <= anchor_time)
+                .max(),
+            AnchorPolicy::Reject | AnchorPolicy::ClampToMonthEnd => guesses.into_iter()
+                .min_by_key(|guess| guess.signed_duration_since(anchor_time).num_seconds().abs()),
+        };
 
-                for guess in [second_guess, third_guess] {
-                    let delta = guess.signed_duration_since(anchor_time).num_seconds().abs();
-                    if delta < final_delta {
-                        final_guess = guess;
-                        final_delta = delta;
-                    }
-                }
+        final_guess_opt.map(|final_guess| MetarTime::DateTime(UtcDateTime(final_guess)))
+    }
+}
 
-                MetarTime::DateTime(UtcDateTime(final_guess))
-            },
+/// Policy of converting a day and time into a full datetime using an anchor time, see [MetarTime::to_date_time()].
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnchorPolicy {
+    /// Nearest datetime, rejected if no nearby month contains the day.
+    #[default]
+    Reject,
+    /// Nearest datetime, the day is clamped to the last day of the month only if no nearby month contains the day,
+    /// e.g. a corrupted day 35.
+    ClampToMonthEnd,
+    /// Latest datetime not after the anchor time, e.g. for processing archives where the anchor time is the time of publication.
+    PastOnly,
+}
+
+impl FromStr for AnchorPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reject" => Ok(AnchorPolicy::Reject),
+            "clamp-to-month-end" => Ok(AnchorPolicy::ClampToMonthEnd),
+            "past-only" => Ok(AnchorPolicy::PastOnly),
+            _ => Err(anyhow!("Invalid anchor policy, given {}", s))
         }
     }
 }
@@ -301,32 +359,45 @@ impl Header {
     }
 }
 
-fn handle_header(text: &str, anchor_time: Option<NaiveDateTime>) -> Option<(Header, usize)> {
+fn handle_header(text: &str, anchor_time: Option<NaiveDateTime>, anchor_policy: AnchorPolicy) -> Result<Option<(Header, usize)>, DecodeError> {
     HEADER_RE.captures(text)
         .map(|capture| {
-            let station_id = Some(capture["station_id"].to_string());
+            let invalid = || DecodeError::Header(InvalidGroup::from_capture(&capture));
+
+            let (header, end) = header_without_time(&capture);
 
             let day = capture["day"].parse().unwrap();
             let hour = capture["hour"].parse().unwrap();
             let minute = capture["minute"].parse().unwrap();
 
-            let naive_time = NaiveTime::from_hms_opt(hour, minute, 0);
-            let mut time = naive_time.map(|nt| MetarTime::DayTime(UtcDayTime(day, nt)));
+            let naive_time = NaiveTime::from_hms_opt(hour, minute, 0).ok_or_else(invalid)?;
+            let mut time = MetarTime::DayTime(UtcDayTime(day, naive_time));
 
             if let Some(at) = anchor_time {
-                time = time.map(|t| t.to_date_time(at));
+                time = time.to_date_time(at, anchor_policy).ok_or_else(invalid)?;
             }
 
-            let is_corrected = Some(capture.name("corrected").is_some());
+            Ok((Header { observation_time: Some(time), ..header }, end))
+        })
+        .transpose()
+}
+
+/// Handles the identification groups leaving out the observation time, used when the observation time is invalid.
+fn handle_header_without_time(text: &str) -> Option<(Header, usize)> {
+    HEADER_RE.captures(text)
+        .map(|capture| header_without_time(&capture))
+}
 
-            let is_automated = Some(capture.name("auto").is_some());
+fn header_without_time(capture: &Captures) -> (Header, usize) {
+    let station_id = Some(capture["station_id"].to_string());
 
-            let end = capture.name("end").unwrap().end();
+    let is_corrected = Some(capture.name("corrected").is_some());
 
-            let header = Header { station_id, observation_time: time, is_corrected, is_automated };
+    let is_automated = Some(capture.name("auto").is_some());
 
-            (header, end)
-        })
+    let end = capture.name("end").unwrap().end();
+
+    (Header { station_id, observation_time: None, is_corrected, is_automated }, end)
 }
 
 /// Unit of a physical quantity.
@@ -391,6 +462,30 @@ pub enum Unit {
     /// ```
     #[serde(rename = "ft")]
     Foot,
+    /// Inch.
+    ///
+    /// JSON representation:
+    /// ```json
+    /// "in"
+    /// ```
+    #[serde(rename = "in")]
+    Inch,
+    /// Centimetre.
+    ///
+    /// JSON representation:
+    /// ```json
+    /// "cm"
+    /// ```
+    #[serde(rename = "cm")]
+    Centimetre,
+    /// Millimetre.
+    ///
+    /// JSON representation:
+    /// ```json
+    /// "mm"
+    /// ```
+    #[serde(rename = "mm")]
+    Millimetre,
     /// Degree Celsius.
     ///
     /// JSON representation:
@@ -415,6 +510,14 @@ pub enum Unit {
     /// ```
     #[serde(rename = "inHg")]
     InchOfMercury,
+    /// Millimetre of mercury.
+    ///
+    /// JSON representation:
+    /// ```json
+    /// "mmHg"
+    /// ```
+    #[serde(rename = "mmHg")]
+    MillimetreOfMercury,
 }
 
 impl FromStr for Unit {
@@ -434,24 +537,39 @@ impl FromStr for Unit {
     }
 }
 
-fn parse_value(s: &str) -> Result<f32> {
-    if s.contains(' ') && s.contains('/') {
-        let mut split_space = s.split(' ');
-        let number: f32 = split_space.next().unwrap().parse()?;
+fn parse_fraction(s: &str) -> Result<Number> {
+    let (numerator, denominator) = s.split_once('/').ok_or_else(|| anyhow!("Invalid fraction, given {}", s))?;
+    let numerator = numerator.parse()?;
+    let denominator = denominator.parse()?;
 
-        let mut split_slash = split_space.next().unwrap().split('/');
-        let numerator: f32 = split_slash.next().unwrap().parse()?;
-        let denominator: f32 = split_slash.next().unwrap().parse()?;
+    Number::new(numerator, denominator).ok_or_else(|| anyhow!("Invalid fraction, given {}", s))
+}
+
+fn parse_value(s: &str) -> Result<Number> {
+    if let (Some((number, fraction)), true) = (s.split_once(' '), s.contains('/')) {
+        let number: i64 = number.parse()?;
+        let fraction = parse_fraction(fraction)?;
 
-        Ok(number + numerator / denominator)
+        Ok(fraction + number)
     } else if s.contains('/') {
-        let mut split = s.split('/');
-        let numerator: f32 = split.next().unwrap().parse()?;
-        let denominator: f32 = split.next().unwrap().parse()?;
+        parse_fraction(s)
+    } else {
+        Number::from_str(s)
+    }
+}
+
+/// Parses the reporting resolution of a value, e.g. 1/4 for `1 3/4` or 1 for `0800`.
+fn parse_resolution(s: &str) -> Result<Number> {
+    let first = s.split_once('V').map_or(s, |(first, _)| first).trim_start_matches(['P', 'M', '-']);
 
-        Ok(numerator / denominator)
+    if let Some((_, denominator)) = first.rsplit_once('/') {
+        Number::new(1, denominator.parse()?).ok_or_else(|| anyhow!("Invalid fraction, given {}", first))
+    } else if let Some((_, decimals)) = first.split_once('.') {
+        10i64.checked_pow(decimals.len() as u32)
+            .and_then(|denominator| Number::new(1, denominator))
+            .ok_or_else(|| anyhow!("Invalid decimal number, given {}", first))
     } else {
-        Ok(s.parse()?)
+        Ok(Number::from(1))
     }
 }
 
@@ -469,11 +587,11 @@ fn parse_value(s: &str) -> Result<f32> {
 #[serde(tag = "value_type", content = "value", rename_all = "snake_case")]
 pub enum ValueInRange {
     /// Above specified number.
-    Above(f32),
+    Above(Number),
     /// Below specified number.
-    Below(f32),
+    Below(Number),
     /// Same as specified number.
-    Exact(f32),
+    Exact(Number),
 }
 
 impl FromStr for ValueInRange {
@@ -481,22 +599,31 @@ impl FromStr for ValueInRange {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Some(stripped) = s.strip_prefix('P') {
-            let value = parse_value(stripped).unwrap();
+            let value = parse_value(stripped)?;
             Ok(ValueInRange::Above(value))
         } else if let Some(stripped) = s.strip_prefix('M') {
-            let value = parse_value(stripped).unwrap();
+            let value = parse_value(stripped)?;
             Ok(ValueInRange::Below(value))
         } else {
-            let value = parse_value(s).unwrap();
+            let value = parse_value(s)?;
             Ok(ValueInRange::Exact(value))
         }
     }
 }
 
-impl Div<f32> for ValueInRange {
+impl ValueInRange {
+    /// Converts the specified number into the nearest [f64].
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            ValueInRange::Above(x) | ValueInRange::Below(x) | ValueInRange::Exact(x) => x.as_f64(),
+        }
+    }
+}
+
+impl Div<i64> for ValueInRange {
     type Output = ValueInRange;
 
-    fn div(self, rhs: f32) -> Self::Output {
+    fn div(self, rhs: i64) -> Self::Output {
         match self {
             ValueInRange::Above(x) => ValueInRange::Above(x / rhs),
             ValueInRange::Below(x) => ValueInRange::Below(x / rhs),
@@ -505,10 +632,22 @@ impl Div<f32> for ValueInRange {
     }
 }
 
-impl Mul<f32> for ValueInRange {
+impl Mul<i64> for ValueInRange {
     type Output = ValueInRange;
 
-    fn mul(self, rhs: f32) -> Self::Output {
+    fn mul(self, rhs: i64) -> Self::Output {
+        match self {
+            ValueInRange::Above(x) => ValueInRange::Above(x * rhs),
+            ValueInRange::Below(x) => ValueInRange::Below(x * rhs),
+            ValueInRange::Exact(x) => ValueInRange::Exact(x * rhs),
+        }
+    }
+}
+
+impl Mul<Number> for ValueInRange {
+    type Output = ValueInRange;
+
+    fn mul(self, rhs: Number) -> Self::Output {
         match self {
             ValueInRange::Above(x) => ValueInRange::Above(x * rhs),
             ValueInRange::Below(x) => ValueInRange::Below(x * rhs),
@@ -533,13 +672,13 @@ pub enum Value {
     /// Variable number.
     Variable,
     /// Above specified number.
-    Above(f32),
+    Above(Number),
     /// Below specified number.
-    Below(f32),
+    Below(Number),
     /// Between specified [`ValueInRange`] values.
     Range(ValueInRange, ValueInRange),
     /// Same as specified number.
-    Exact(f32),
+    Exact(Number),
 }
 
 impl FromStr for Value {
@@ -548,28 +687,37 @@ impl FromStr for Value {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s == "VRB" {
             Ok(Value::Variable)
-        } else if s.contains('V') {
-            let mut split = s.split('V');
-            let value1 = ValueInRange::from_str(split.next().unwrap()).unwrap();
-            let value2 = ValueInRange::from_str(split.next().unwrap()).unwrap();
+        } else if let Some((first, second)) = s.split_once('V') {
+            let value1 = ValueInRange::from_str(first)?;
+            let value2 = ValueInRange::from_str(second)?;
             Ok(Value::Range(value1, value2))
         } else if let Some(stripped) = s.strip_prefix('P') {
-            let value = parse_value(stripped).unwrap();
+            let value = parse_value(stripped)?;
             Ok(Value::Above(value))
         } else if let Some(stripped) = s.strip_prefix('M') {
-            let value = parse_value(stripped).unwrap();
+            let value = parse_value(stripped)?;
             Ok(Value::Below(value))
         } else {
-            let value = parse_value(s).unwrap();
+            let value = parse_value(s)?;
             Ok(Value::Exact(value))
         }
     }
 }
 
-impl Div<f32> for Value {
+impl Value {
+    /// Converts the specified number into the nearest [f64], [None] for [Value::Variable] and [Value::Range].
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Above(x) | Value::Below(x) | Value::Exact(x) => Some(x.as_f64()),
+            Value::Variable | Value::Range(_, _) => None,
+        }
+    }
+}
+
+impl Div<i64> for Value {
     type Output = Value;
 
-    fn div(self, rhs: f32) -> Self::Output {
+    fn div(self, rhs: i64) -> Self::Output {
         match self {
             Value::Variable => Value::Variable,
             Value::Above(x) => Value::Above(x / rhs),
@@ -580,10 +728,24 @@ impl Div<f32> for Value {
     }
 }
 
-impl Mul<f32> for Value {
+impl Mul<i64> for Value {
     type Output = Value;
 
-    fn mul(self, rhs: f32) -> Self::Output {
+    fn mul(self, rhs: i64) -> Self::Output {
+        match self {
+            Value::Variable => Value::Variable,
+            Value::Above(x) => Value::Above(x * rhs),
+            Value::Below(x) => Value::Below(x * rhs),
+            Value::Range(x, y) => Value::Range(x * rhs, y * rhs),
+            Value::Exact(x) => Value::Exact(x * rhs),
+        }
+    }
+}
+
+impl Mul<Number> for Value {
+    type Output = Value;
+
+    fn mul(self, rhs: Number) -> Self::Output {
         match self {
             Value::Variable => Value::Variable,
             Value::Above(x) => Value::Above(x * rhs),
@@ -604,18 +766,69 @@ pub struct Quantity {
     #[serde(flatten)]
     pub value: Value,
     pub units: Unit,
+    /// Resolution of the reported value, e.g. 100 ft for cloud heights or 1/4 mi for `1 3/4SM`.
+    pub resolution: Option<Number>,
 }
 
 impl Quantity {
     fn new(value: Value, units: Unit) -> Quantity {
-        Quantity { value, units }
+        Quantity { value, units, resolution: None }
+    }
+
+    /// Parses a reported value together with its resolution.
+    fn parse(s: &str, units: Unit) -> Result<Quantity> {
+        let value = Value::from_str(s)?;
+
+        let resolution = match value {
+            Value::Variable => None,
+            _ => Some(parse_resolution(s)?),
+        };
+
+        Ok(Quantity { value, units, resolution })
+    }
+
+    /// Parses a reported direction in degrees, reported in steps of 10 degrees (e.g. `270`).
+    fn parse_direction(s: &str) -> Result<Quantity> {
+        let quantity = Quantity::parse(s, Unit::DegreeTrue)?;
+
+        Ok(Quantity { resolution: quantity.resolution.map(|_| Number::from(10)), ..quantity })
     }
+}
+
+impl Div<i64> for Quantity {
+    type Output = Quantity;
 
-    fn new_opt(value: Option<Value>, units: Unit) -> Option<Quantity> {
-        value.map(|v| Quantity { value: v, units })
+    fn div(self, rhs: i64) -> Self::Output {
+        Quantity { value: self.value / rhs, units: self.units, resolution: self.resolution.map(|r| r / rhs) }
     }
 }
 
+impl Mul<i64> for Quantity {
+    type Output = Quantity;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Quantity { value: self.value * rhs, units: self.units, resolution: self.resolution.map(|r| r * rhs) }
+    }
+}
+
+/// Reason why a value is not available, modelled on the IWXXM `nilReason` code list.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NilReason {
+    /// The value has no meaning in the reported conditions, e.g. the wind direction of a calm wind.
+    Inapplicable,
+    /// The group was reported but its value is missing, e.g. `////` or `XX`.
+    Missing,
+    /// The value could not be observed, e.g. clouds reported as `//////` by an automated station.
+    NotObservable,
+    /// The group was not included in the report at all.
+    /// Used only for the mandatory groups of the main section, not for the groups of TREND changes.
+    NotReported,
+}
+
 /// Surface wind groups.
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
@@ -627,6 +840,9 @@ pub struct Wind {
     pub wind_from_direction_range: Option<Quantity>,
     pub wind_speed: Option<Quantity>,
     pub wind_gust: Option<Quantity>,
+    pub wind_from_direction_nil_reason: Option<NilReason>,
+    pub wind_speed_nil_reason: Option<NilReason>,
+    pub wind_gust_nil_reason: Option<NilReason>,
 }
 
 impl Wind {
@@ -635,45 +851,54 @@ impl Wind {
     }
 }
 
-fn handle_wind(text: &str) -> Option<(Wind, usize)> {
+fn handle_wind(text: &str) -> Result<Option<(Wind, usize)>, DecodeError> {
     WIND_RE.captures(text)
         .map(|capture| {
-            let mut from_direction_value = match &capture["direction"] {
-                "///" => None,
-                s => Some(Value::from_str(s).unwrap()),
+            let invalid = || DecodeError::Wind(InvalidGroup::from_capture(&capture));
+
+            let units = Unit::from_str(&capture["units"]).unwrap();
+
+            let (mut wind_from_direction, mut wind_from_direction_nil_reason) = match &capture["direction"] {
+                "///" => (None, Some(NilReason::Missing)),
+                s => (Some(Quantity::parse_direction(s).map_err(|_| invalid())?), None),
             };
 
             if &capture["direction"] == "000" && &capture["speed"] == "00" {
                 // calm wind has no direction
-                from_direction_value = None;
+                wind_from_direction = None;
+                wind_from_direction_nil_reason = Some(NilReason::Inapplicable);
             }
 
-            let speed_value = match &capture["speed"] {
-                "//" => None,
-                s => Some(Value::from_str(s).unwrap()),
+            let (wind_speed, wind_speed_nil_reason) = match &capture["speed"] {
+                "//" => (None, Some(NilReason::Missing)),
+                s => (Some(Quantity::parse(s, units).map_err(|_| invalid())?), None),
             };
 
-            let gust_value = capture.name("gust").and_then(|c| match c.as_str() {
-                "//" => None,
-                s => Some(Value::from_str(s).unwrap()),
-            });
-
-            let units = Unit::from_str(&capture["units"]).unwrap();
-
-            let from_direction_range_value = capture.name("direction_range")
-                .map(|s| Value::from_str(s.as_str()).unwrap());
+            let (wind_gust, wind_gust_nil_reason) = match capture.name("gust").map(|c| c.as_str()) {
+                Some("//") => (None, Some(NilReason::Missing)),
+                Some(s) => (Some(Quantity::parse(s, units).map_err(|_| invalid())?), None),
+                None => (None, None),
+            };
 
-            let wind_from_direction = Quantity::new_opt(from_direction_value, Unit::DegreeTrue);
-            let wind_from_direction_range = Quantity::new_opt(from_direction_range_value, Unit::DegreeTrue);
-            let wind_speed = Quantity::new_opt(speed_value, units);
-            let wind_gust = Quantity::new_opt(gust_value, units);
+            let wind_from_direction_range = capture.name("direction_range")
+                .map(|c| Quantity::parse_direction(c.as_str()).map_err(|_| invalid()))
+                .transpose()?;
 
             let end = capture.name("end").unwrap().end();
 
-            let wind = Wind { wind_from_direction, wind_from_direction_range, wind_speed, wind_gust };
+            let wind = Wind {
+                wind_from_direction,
+                wind_from_direction_range,
+                wind_speed,
+                wind_gust,
+                wind_from_direction_nil_reason,
+                wind_speed_nil_reason,
+                wind_gust_nil_reason,
+            };
 
-            (wind, end)
+            Ok((wind, end))
         })
+        .transpose()
 }
 
 /// Direction octant.
@@ -726,6 +951,12 @@ pub struct Visibility {
     pub prevailing_visibility: Option<Quantity>,
     pub minimum_visibility: Option<Quantity>,
     pub directional_visibilites: Vec<DirectionalVisibility>,
+    pub prevailing_visibility_nil_reason: Option<NilReason>,
+    /// Flag if CAVOK (ceiling and visibility OK) was reported.
+    /// In such case, the prevailing visibility is set to 10 km or more.
+    pub is_cavok: bool,
+    /// Flag if no directional variation of the visibility (NDV) was reported.
+    pub no_directional_variation: bool,
 }
 
 impl Visibility {
@@ -734,50 +965,184 @@ impl Visibility {
     }
 }
 
-fn handle_visibility(text: &str) -> Option<(Visibility, bool, usize)> {
+fn handle_visibility(text: &str) -> Result<Option<(Visibility, usize)>, DecodeError> {
     VISIBILITY_RE.captures(text)
         .map(|capture| {
+            let invalid = || DecodeError::Visibility(InvalidGroup::from_capture(&capture));
+
             let mut is_cavok = false;
+            let mut prevailing_visibility_nil_reason = None;
 
-            let mut prevailing_visibility_value = match &capture["prevailing"] {
-                "////" => None,
+            let units = capture.name("units")
+                .map(|c| Unit::from_str(c.as_str()).unwrap())
+                .unwrap_or(Unit::Metre);
+
+            let mut prevailing_visibility = match &capture["prevailing"] {
+                "////" => {
+                    prevailing_visibility_nil_reason = Some(NilReason::Missing);
+                    None
+                },
                 "CAVOK" | "KAVOK" => {
                     is_cavok = true;
-                    Some(Value::Above(10000.0))
+                    Some(Quantity::new(Value::Above(Number::from(10000)), units))
                 },
-                s => Some(Value::from_str(s).unwrap()),
+                s => Some(Quantity::parse(s, units).map_err(|_| invalid())?),
             };
 
-            let units = capture.name("units")
-                .map(|c| Unit::from_str(c.as_str()).unwrap())
-                .unwrap_or(Unit::Metre);
-
-            if prevailing_visibility_value == Some(Value::Exact(9999.0)) && units == Unit::Metre {
-                prevailing_visibility_value = Some(Value::Above(10000.0));
+            if let Some(pv) = prevailing_visibility.as_mut() {
+                if pv.value == Value::Exact(Number::from(9999)) && units == Unit::Metre {
+                    pv.value = Value::Above(Number::from(10000));
+                }
             }
 
-            let minimum_visibility_value = capture.name("minimum").map(|c| Value::from_str(c.as_str()).unwrap());
+            let minimum_visibility = capture.name("minimum")
+                .map(|c| Quantity::parse(c.as_str(), units).map_err(|_| invalid()))
+                .transpose()?;
 
             let directional_visibilites = capture.name("directional")
                 .map(|c| c.as_str().split(' ')
-                    .map(|group| DIRECTIONAL_VISIBILITY_RE.captures(group))
-                    .filter(|capture| capture.is_some())
-                    .map(|capture| DirectionalVisibility {
-                        visibility: Quantity::new(Value::from_str(&capture.as_ref().unwrap()["visibility"]).unwrap(), units),
-                        direction: DirectionOctant::from_str(&capture.unwrap()["direction"]).unwrap(),
-                    })
-                    .collect::<Vec<_>>())
+                    .filter_map(|group| DIRECTIONAL_VISIBILITY_RE.captures(group))
+                    .map(|capture| Ok(DirectionalVisibility {
+                        visibility: Quantity::parse(&capture["visibility"], units).map_err(|_| invalid())?,
+                        direction: DirectionOctant::from_str(&capture["direction"]).unwrap(),
+                    }))
+                    .collect::<Result<Vec<_>, _>>())
+                .transpose()?
                 .unwrap_or_default();
 
-            let prevailing_visibility = Quantity::new_opt(prevailing_visibility_value, units);
-            let minimum_visibility = Quantity::new_opt(minimum_visibility_value, units);
+            let no_directional_variation = capture.name("ndv").is_some();
 
             let end = capture.name("end").unwrap().end();
 
-            let visibility = Visibility { prevailing_visibility, minimum_visibility, directional_visibilites };
+            let visibility = Visibility {
+                prevailing_visibility,
+                minimum_visibility,
+                directional_visibilites,
+                prevailing_visibility_nil_reason,
+                is_cavok,
+                no_directional_variation,
+            };
 
-            (visibility, is_cavok, end)
+            Ok((visibility, end))
         })
+        .transpose()
+}
+
+/// Parallel runway designator.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunwayParallel {
+    Left,
+    Center,
+    Right,
+}
+
+impl RunwayParallel {
+    /// Returns the designator of the same parallel runway when used in the opposite direction.
+    pub fn reciprocal(&self) -> RunwayParallel {
+        match self {
+            RunwayParallel::Left => RunwayParallel::Right,
+            RunwayParallel::Center => RunwayParallel::Center,
+            RunwayParallel::Right => RunwayParallel::Left,
+        }
+    }
+}
+
+impl FromStr for RunwayParallel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "L" => Ok(RunwayParallel::Left),
+            "C" => Ok(RunwayParallel::Center),
+            "R" => Ok(RunwayParallel::Right),
+            _ => Err(anyhow!("Invalid runway parallel designator, given {}", s))
+        }
+    }
+}
+
+/// Runway designator.
+///
+/// JSON representation is internally tagged and in lowercase snake case. Example:
+/// ```json
+/// {
+///     "runway_type": "designated",
+///     "number": 27,
+///     "parallel": "left"
+/// }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "runway_type", rename_all = "snake_case")]
+pub enum Runway {
+    /// Runway with a number (magnetic heading in tens of degrees) and an optional parallel designator.
+    Designated {
+        number: u8,
+        parallel: Option<RunwayParallel>,
+    },
+    /// All runways, reported as `ALL RWY` or with the runway number `88`.
+    All,
+    /// Runway information repeated from the previous report, reported with the runway number `99`.
+    Repeated,
+    /// Runway with a non-standard designator kept as reported, e.g. the runway number `45`.
+    Other {
+        designator: String,
+    },
+}
+
+impl Runway {
+    /// Returns the magnetic heading of the runway in degrees.
+    pub fn heading(&self) -> Option<u16> {
+        match self {
+            Runway::Designated { number, .. } => Some(*number as u16 * 10),
+            _ => None,
+        }
+    }
+
+    /// Returns the same runway when used in the opposite direction, e.g. `09R` for `27L`.
+    pub fn reciprocal(&self) -> Option<Runway> {
+        match self {
+            Runway::Designated { number, parallel } => Some(Runway::Designated {
+                number: (number + 17) % 36 + 1,
+                parallel: parallel.map(|p| p.reciprocal()),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the magnetic heading of the runway in the opposite direction in degrees.
+    pub fn reciprocal_heading(&self) -> Option<u16> {
+        self.reciprocal().and_then(|r| r.heading())
+    }
+}
+
+impl FromStr for Runway {
+    type Err = Error;
+
+    /// Parses a runway designator, designators which are not standard are kept as [Runway::Other].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(anyhow!("Invalid runway, given {}", s));
+        }
+
+        let (number, parallel) = s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()));
+
+        let runway = match (number.parse::<u8>(), parallel) {
+            (Ok(88), "") => Runway::All,
+            (Ok(99), "") => Runway::Repeated,
+            (Ok(number @ 1..=36), "") => Runway::Designated { number, parallel: None },
+            (Ok(number @ 1..=36), p) => match RunwayParallel::from_str(p) {
+                Ok(parallel) => Runway::Designated { number, parallel: Some(parallel) },
+                Err(_) => Runway::Other { designator: s.to_string() },
+            },
+            _ => Runway::Other { designator: s.to_string() },
+        };
+
+        Ok(runway)
+    }
 }
 
 /// Runway visual range (RVR) trend.
@@ -809,33 +1174,34 @@ impl FromStr for RunwayVisualRangeTrend {
 #[non_exhaustive]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RunwayVisualRange {
-    pub runway: String,
+    pub runway: Runway,
     pub visual_range: Quantity,
     pub trend: Option<RunwayVisualRangeTrend>,
 }
 
-fn handle_runway_visual_range(text: &str) -> Option<(RunwayVisualRange, usize)> {
+fn handle_runway_visual_range(text: &str) -> Result<Option<(RunwayVisualRange, usize)>, DecodeError> {
     RUNWAY_VISUAL_RANGE_RE.captures(text)
         .map(|capture| {
-            let runway = capture["runway"].to_string();
+            let invalid = || DecodeError::RunwayVisualRange(InvalidGroup::from_capture(&capture));
 
-            let visual_range_value = Value::from_str(&capture["visual_range"]).unwrap();
+            let runway = Runway::from_str(&capture["runway"]).map_err(|_| invalid())?;
 
             let units = capture.name("units")
                 .map(|c| Unit::from_str(c.as_str()).unwrap())
                 .unwrap_or(Unit::Metre);
 
+            let visual_range = Quantity::parse(&capture["visual_range"], units).map_err(|_| invalid())?;
+
             let trend = capture.name("trend")
                 .map(|c| RunwayVisualRangeTrend::from_str(c.as_str()).unwrap());
 
-            let visual_range = Quantity::new(visual_range_value, units);
-
             let end = capture.name("end").unwrap().end();
 
             let rvr = RunwayVisualRange { runway, visual_range, trend };
 
-            (rvr, end)
+            Ok((rvr, end))
         })
+        .transpose()
 }
 
 /// Weather intensity.
@@ -1038,8 +1404,6 @@ pub enum CloudCover {
     Overcast,
     /// Obscured sky but vertical visibility is available.
     VerticalVisibility,
-    /// No cloud of operational significance in CAVOK conditions.
-    CeilingOk,
 }
 
 impl FromStr for CloudCover {
@@ -1079,10 +1443,14 @@ pub enum CloudType {
     Cirrus,
     Cirrostratus,
     Cumulus,
+    /// Cumulus fractus, reported only in the Canadian cloud remarks.
+    CumulusFractus,
     Nimbostratus,
     Stratocumulus,
     StratocumulusLenticularis,
     Stratus,
+    /// Stratus fractus, reported only in the Canadian cloud remarks.
+    StratusFractus,
     ToweringCumulus,
 }
 
@@ -1102,10 +1470,12 @@ impl FromStr for CloudType {
             "CI" => Ok(CloudType::Cirrus),
             "CS" => Ok(CloudType::Cirrostratus),
             "CU" => Ok(CloudType::Cumulus),
+            "CF" => Ok(CloudType::CumulusFractus),
             "NS" => Ok(CloudType::Nimbostratus),
             "SC" => Ok(CloudType::Stratocumulus),
             "SCSL" => Ok(CloudType::StratocumulusLenticularis),
             "ST" => Ok(CloudType::Stratus),
+            "SF" => Ok(CloudType::StratusFractus),
             "TCU" | "TU" => Ok(CloudType::ToweringCumulus),
             _ => Err(anyhow!("Invalid cloud type, given {s}"))
         }
@@ -1120,40 +1490,54 @@ pub struct CloudLayer {
     /// Height above the ground level (AGL).
     pub height: Option<Quantity>,
     pub cloud_type: Option<CloudType>,
+    pub cover_nil_reason: Option<NilReason>,
+    pub height_nil_reason: Option<NilReason>,
+    pub cloud_type_nil_reason: Option<NilReason>,
 }
 
 impl CloudLayer {
+    /// Checks if the layer was reported only by solidi (`//////`), such layer is kept for its nil reasons
+    /// but does not describe any cloud.
     fn is_empty(&self) -> bool {
         self.cover.is_none() && self.height.is_none() && self.cloud_type.is_none()
     }
 }
 
-fn handle_cloud_layer(text: &str) -> Option<(CloudLayer, usize)> {
+fn handle_cloud_layer(text: &str) -> Result<Option<(CloudLayer, usize)>, DecodeError> {
     CLOUD_RE.captures(text)
         .map(|capture| {
-            let cover = match &capture["cover"] {
-                "///" => None,
-                s => Some(CloudCover::from_str(s).unwrap()),
+            let invalid = || DecodeError::Cloud(InvalidGroup::from_capture(&capture));
+
+            let (cover, cover_nil_reason) = match &capture["cover"] {
+                "///" => (None, Some(NilReason::NotObservable)),
+                s => (Some(CloudCover::from_str(s).unwrap()), None),
             };
 
-            let height_value = capture.name("height").and_then(|c| match c.as_str() {
-                "///" => None,
-                s => Some(Value::from_str(s).unwrap() * 100.0),
-            });
+            let (height, mut height_nil_reason) = match capture.name("height").map(|c| c.as_str()) {
+                Some("///") => (None, Some(NilReason::NotObservable)),
+                Some(s) => (Some(Quantity::parse(s, Unit::Foot).map_err(|_| invalid())? * 100), None),
+                None => (None, None),
+            };
 
-            let cloud_type = capture.name("cloud").and_then(|c| match c.as_str() {
-                "///" => None,
-                s => Some(CloudType::from_str(s).unwrap()),
-            });
+            let (cloud_type, mut cloud_type_nil_reason) = match capture.name("cloud").map(|c| c.as_str()) {
+                Some("///") => (None, Some(NilReason::NotObservable)),
+                Some(s) => (Some(CloudType::from_str(s).unwrap()), None),
+                None => (None, None),
+            };
 
-            let height = Quantity::new_opt(height_value, Unit::Foot);
+            if matches!(cover, Some(CloudCover::Clear | CloudCover::SkyClear | CloudCover::NilSignificantCloud | CloudCover::NoCloudDetected)) {
+                // no cloud layer to describe
+                height_nil_reason = Some(NilReason::Inapplicable);
+                cloud_type_nil_reason = Some(NilReason::Inapplicable);
+            }
 
             let end = capture.name("end").unwrap().end();
 
-            let cloud_layer = CloudLayer { cover, height, cloud_type };
+            let cloud_layer = CloudLayer { cover, height, cloud_type, cover_nil_reason, height_nil_reason, cloud_type_nil_reason };
 
-            (cloud_layer, end)
+            Ok((cloud_layer, end))
         })
+        .transpose()
 }
 
 /// Temperature groups.
@@ -1162,6 +1546,8 @@ fn handle_cloud_layer(text: &str) -> Option<(CloudLayer, usize)> {
 pub struct Temperature {
     pub temperature: Option<Quantity>,
     pub dew_point: Option<Quantity>,
+    pub temperature_nil_reason: Option<NilReason>,
+    pub dew_point_nil_reason: Option<NilReason>,
 }
 
 impl Temperature {
@@ -1170,28 +1556,29 @@ impl Temperature {
     }
 }
 
-fn handle_temperature(text: &str) -> Option<(Temperature, usize)> {
+fn handle_temperature(text: &str) -> Result<Option<(Temperature, usize)>, DecodeError> {
     TEMPERATURE_RE.captures(text)
         .map(|capture| {
-            let temperature_value = match &capture["temperature"] {
-                "//" | "XX" => None,
-                s => Some(Value::from_str(&s.replace('M', "-")).unwrap()),
-            };
+            let invalid = || DecodeError::Temperature(InvalidGroup::from_capture(&capture));
 
-            let dew_point_value = capture.name("dew_point").and_then(|c| match c.as_str() {
-                "//" | "XX" => None,
-                s => Some(Value::from_str(&s.replace('M', "-")).unwrap()),
-            });
+            let (temperature, temperature_nil_reason) = match &capture["temperature"] {
+                "//" | "XX" => (None, Some(NilReason::Missing)),
+                s => (Some(Quantity::parse(&s.replace('M', "-"), Unit::DegreeCelsius).map_err(|_| invalid())?), None),
+            };
 
-            let temperature = Quantity::new_opt(temperature_value, Unit::DegreeCelsius);
-            let dew_point = Quantity::new_opt(dew_point_value, Unit::DegreeCelsius);
+            let (dew_point, dew_point_nil_reason) = match capture.name("dew_point").map(|c| c.as_str()) {
+                Some("//" | "XX") => (None, Some(NilReason::Missing)),
+                Some(s) => (Some(Quantity::parse(&s.replace('M', "-"), Unit::DegreeCelsius).map_err(|_| invalid())?), None),
+                None => (None, Some(NilReason::NotReported)),
+            };
 
             let end = capture.name("end").unwrap().end();
 
-            let temperature = Temperature { temperature, dew_point };
+            let temperature = Temperature { temperature, dew_point, temperature_nil_reason, dew_point_nil_reason };
 
-            (temperature, end)
+            Ok((temperature, end))
         })
+        .transpose()
 }
 
 /// Pressure group.
@@ -1199,6 +1586,7 @@ fn handle_temperature(text: &str) -> Option<(Temperature, usize)> {
 #[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub struct Pressure {
     pub pressure: Option<Quantity>,
+    pub pressure_nil_reason: Option<NilReason>,
 }
 
 impl Pressure {
@@ -1207,51 +1595,109 @@ impl Pressure {
     }
 }
 
-fn handle_pressure(text: &str) -> Option<(Pressure, usize)> {
+fn handle_pressure(text: &str) -> Result<Option<(Pressure, usize)>, DecodeError> {
     PRESSURE_RE.captures(text)
         .map(|capture| {
-            let mut pressure_value = match &capture["pressure"] {
-                "////" => None,
-                s => Some(Value::from_str(s).unwrap()),
-            };
+            let invalid = || DecodeError::Pressure(InvalidGroup::from_capture(&capture));
 
             let units = Unit::from_str(&capture["units"]).unwrap();
 
+            let (mut pressure, pressure_nil_reason) = match &capture["pressure"] {
+                "////" => (None, Some(NilReason::Missing)),
+                s => (Some(Quantity::parse(s, units).map_err(|_| invalid())?), None),
+            };
+
             if units == Unit::InchOfMercury {
-                pressure_value = pressure_value.map(|p| p / 100.0)
+                pressure = pressure.map(|p| p / 100)
             }
 
-            let pressure = Quantity::new_opt(pressure_value, units);
-
             let end = capture.name("end").unwrap().end();
 
-            let pressure = Pressure { pressure };
+            let pressure = Pressure { pressure, pressure_nil_reason };
 
-            (pressure, end)
+            Ok((pressure, end))
         })
+        .transpose()
+}
+
+/// Flight phase affected by a wind shear.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindShearPhase {
+    TakeOff,
+    Landing,
+}
+
+impl FromStr for WindShearPhase {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "TKOF" => Ok(WindShearPhase::TakeOff),
+            "LDG" => Ok(WindShearPhase::Landing),
+            _ => Err(anyhow!("Invalid wind shear phase, given {}", s))
+        }
+    }
 }
 
 /// Wind shear group.
+///
+/// Either a wind shear along a runway (e.g. `WS R27L`, `WS TKOF RWY09`)
+/// or a low-level wind shear given by its height and wind (e.g. `WS020/27045KT`).
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct WindShear {
-    pub runway: String,
+    pub runway: Option<Runway>,
+    /// Flight phase (take-off or landing) if the wind shear is reported only for one of them.
+    pub phase: Option<WindShearPhase>,
+    /// Height above the ground level (AGL) of the low-level wind shear.
+    pub height: Option<Quantity>,
+    /// Wind direction at the height of the low-level wind shear.
+    /// Reported by the direction from which the wind originates.
+    pub wind_from_direction: Option<Quantity>,
+    /// Wind speed at the height of the low-level wind shear.
+    pub wind_speed: Option<Quantity>,
 }
 
-fn handle_wind_shear(text: &str) -> Option<(WindShear, usize)> {
+fn handle_wind_shear(text: &str) -> Result<Option<(WindShear, usize)>, DecodeError> {
+    if let Some(capture) = LOW_LEVEL_WIND_SHEAR_RE.captures(text) {
+        let invalid = || DecodeError::WindShear(InvalidGroup::from_capture(&capture));
+
+        let units = Unit::from_str(&capture["units"]).unwrap();
+
+        let height = Some(Quantity::parse(&capture["height"], Unit::Foot).map_err(|_| invalid())? * 100);
+        let wind_from_direction = Some(Quantity::parse_direction(&capture["direction"]).map_err(|_| invalid())?);
+        let wind_speed = Some(Quantity::parse(&capture["speed"], units).map_err(|_| invalid())?);
+
+        let end = capture.name("end").unwrap().end();
+
+        let ws = WindShear { height, wind_from_direction, wind_speed, ..Default::default() };
+
+        return Ok(Some((ws, end)));
+    }
+
     WIND_SHEAR_RE.captures(text)
         .map(|capture| {
+            let invalid = || DecodeError::WindShear(InvalidGroup::from_capture(&capture));
+
+            let phase = capture.name("phase")
+                .map(|c| WindShearPhase::from_str(c.as_str()).unwrap());
+
             let runway = match &capture["runway"] {
-                "ALL RWY" => "all".to_string(),
-                s => s[1..].to_string(),
+                "ALL RWY" | "ALL RWYS" => Runway::All,
+                s => Runway::from_str(s.trim_start_matches("RWY").trim_start_matches('R')).map_err(|_| invalid())?,
             };
 
             let end = capture.name("end").unwrap().end();
 
-            let ws = WindShear { runway };
+            let ws = WindShear { runway: Some(runway), phase, ..Default::default() };
 
-            (ws, end)
+            Ok((ws, end))
         })
+        .transpose()
 }
 
 /// Sea state from WMO Code Table 3700.
@@ -1300,6 +1746,9 @@ pub struct Sea {
     pub sea_temperature: Option<Quantity>,
     pub sea_state: Option<SeaState>,
     pub wave_height: Option<Quantity>,
+    pub sea_temperature_nil_reason: Option<NilReason>,
+    pub sea_state_nil_reason: Option<NilReason>,
+    pub wave_height_nil_reason: Option<NilReason>,
 }
 
 impl Sea {
@@ -1308,33 +1757,42 @@ impl Sea {
     }
 }
 
-fn handle_sea(text: &str) -> Option<(Sea, usize)> {
+fn handle_sea(text: &str) -> Result<Option<(Sea, usize)>, DecodeError> {
     SEA_RE.captures(text)
         .map(|capture| {
-            let temperature_value = match &capture["temperature"] {
-                "//" | "XX" => None,
-                s => Some(Value::from_str(&s.replace('M', "-")).unwrap()),
-            };
+            let invalid = || DecodeError::Sea(InvalidGroup::from_capture(&capture));
 
-            let sea_state = capture.name("state").and_then(|c| match c.as_str() {
-                "/" => None,
-                s => Some(SeaState::from_str(s).unwrap()),
-            });
+            let (sea_temperature, sea_temperature_nil_reason) = match &capture["temperature"] {
+                "//" | "XX" => (None, Some(NilReason::Missing)),
+                s => (Some(Quantity::parse(&s.replace('M', "-"), Unit::DegreeCelsius).map_err(|_| invalid())?), None),
+            };
 
-            let height_value = capture.name("height").and_then(|c| match c.as_str() {
-                "///" => None,
-                s => Some(Value::from_str(s).unwrap() / 10.0),
-            });
+            let (sea_state, sea_state_nil_reason) = match capture.name("state").map(|c| c.as_str()) {
+                Some("/") => (None, Some(NilReason::Missing)),
+                Some(s) => (Some(SeaState::from_str(s).unwrap()), None),
+                None => (None, None),
+            };
 
-            let sea_temperature = Quantity::new_opt(temperature_value, Unit::DegreeCelsius);
-            let wave_height = Quantity::new_opt(height_value, Unit::Metre);
+            let (wave_height, wave_height_nil_reason) = match capture.name("height").map(|c| c.as_str()) {
+                Some("///") => (None, Some(NilReason::Missing)),
+                Some(s) => (Some(Quantity::parse(s, Unit::Metre).map_err(|_| invalid())? / 10), None),
+                None => (None, None),
+            };
 
             let end = capture.name("end").unwrap().end();
 
-            let sea = Sea { sea_temperature, sea_state, wave_height };
+            let sea = Sea {
+                sea_temperature,
+                sea_state,
+                wave_height,
+                sea_temperature_nil_reason,
+                sea_state_nil_reason,
+                wave_height_nil_reason,
+            };
 
-            (sea, end)
+            Ok((sea, end))
         })
+        .transpose()
 }
 
 fn handle_color(text: &str) -> Option<usize> {
@@ -1387,10 +1845,13 @@ struct TrendTime {
     time: Option<MetarTime>,
 }
 
-fn handle_trend_time(text: &str, anchor_time: Option<NaiveDateTime>) -> Option<(TrendTime, usize)> {
+fn handle_trend_time(text: &str, anchor_time: Option<NaiveDateTime>) -> Result<Option<(TrendTime, usize)>, DecodeError> {
     TREND_TIME_RE.captures(text)
         .map(|capture| {
-            let indicator = TrendTimeIndicator::from_str(&capture["indicator"]).unwrap();
+            let invalid = || DecodeError::TrendTime(InvalidGroup::from_capture(&capture));
+
+            let (trend_time, end) = trend_time_without_time(&capture);
+
             let mut hour = capture["hour"].parse().unwrap();
             let minute = capture["minute"].parse().unwrap();
 
@@ -1398,24 +1859,37 @@ fn handle_trend_time(text: &str, anchor_time: Option<NaiveDateTime>) -> Option<(
                 hour = 0;
             }
 
-            let naive_time = NaiveTime::from_hms_opt(hour, minute, 0);
-            let mut time = naive_time.map(|nt| MetarTime::Time(UtcTime(nt)));
+            let naive_time = NaiveTime::from_hms_opt(hour, minute, 0).ok_or_else(invalid)?;
+            let mut time = MetarTime::Time(UtcTime(naive_time));
 
+            // times of the forecast are not restricted by the anchor policy
             if let Some(at) = anchor_time {
-                time = time.map(|t| t.to_date_time(at));
+                time = time.to_date_time(at, AnchorPolicy::default()).ok_or_else(invalid)?;
             }
 
-            let end = capture.name("end").unwrap().end();
+            Ok((TrendTime { time: Some(time), ..trend_time }, end))
+        })
+        .transpose()
+}
 
-            let trend_time = TrendTime { indicator, time };
+/// Handles the time group of a TREND forecast leaving out the time, used when the time is invalid.
+fn handle_trend_time_without_time(text: &str) -> Option<(TrendTime, usize)> {
+    TREND_TIME_RE.captures(text)
+        .map(|capture| trend_time_without_time(&capture))
+}
 
-            (trend_time, end)
-        })
+fn trend_time_without_time(capture: &Captures) -> (TrendTime, usize) {
+    let indicator = TrendTimeIndicator::from_str(&capture["indicator"]).unwrap();
+
+    let end = capture.name("end").unwrap().end();
+
+    (TrendTime { indicator, time: None }, end)
 }
 
 /// Significant changes in the meteorological conditions in the TREND forecast.
 ///
 /// Only elements for which a significant change is expected are [Option::Some].
+/// Expected CAVOK conditions and no directional variation of the visibility are flagged in the visibility groups.
 #[non_exhaustive]
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct TrendChange {
@@ -1437,6 +1911,23 @@ pub struct TrendChange {
     pub clouds: Vec<CloudLayer>,
 }
 
+impl TrendChange {
+    /// Returns the kind of the last decoded group in the canonical order.
+    fn last_group(&self) -> Option<GroupKind> {
+        if !self.clouds.is_empty() {
+            Some(GroupKind::Cloud)
+        } else if !self.weather.is_empty() {
+            Some(GroupKind::PresentWeather)
+        } else if !self.visibility.is_empty() {
+            Some(GroupKind::Visibility)
+        } else if !self.wind.is_empty() {
+            Some(GroupKind::Wind)
+        } else {
+            None
+        }
+    }
+}
+
 /// Decoded METAR report.
 #[non_exhaustive]
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
@@ -1477,48 +1968,411 @@ pub struct Metar {
     #[serde(flatten)]
     pub sea: Sea,
     pub trend_changes: Vec<TrendChange>,
+    /// Groups of the main and TREND sections decoded by custom group handlers, keyed by the handler name.
+    /// See [Decoder::group_handler()].
+    pub extensions: BTreeMap<String, Vec<serde_json::Value>>,
+    /// Remark section.
+    pub remarks: Remarks,
+    /// Sensor health decoded from the remark section.
+    pub sensor_status: SensorStatus,
+    /// Undecoded groups and warnings of the decoding.
+    pub diagnostics: Diagnostics,
+    /// Source spans of the decoded elements, [Some] only if enabled by [DecodeOptions::provenance()].
+    ///
+    /// JSON representation is omitted if [None].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    /// Violations of the WMO FM 15 conformance, [Some] only if enabled by [DecodeOptions::strict()].
+    ///
+    /// JSON representation is omitted if [None].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub violations: Option<Vec<Violation>>,
     pub report: String,
 }
 
+impl Metar {
+    /// Records the span of a decoded element if the provenance is enabled.
+    fn record_span(&mut self, path: fmt::Arguments, span: Range<usize>) {
+        if let Some(provenance) = self.provenance.as_mut() {
+            provenance.record(path, span, &self.report);
+        }
+    }
+
+    /// Marks values of the mandatory groups which were not included in the report at all.
+    ///
+    /// TREND changes are not filled, a group omitted in a TREND forecast means that no change is expected.
+    fn fill_not_reported(&mut self) {
+        fn fill(value: &Option<Quantity>, nil_reason: &mut Option<NilReason>) {
+            if value.is_none() && nil_reason.is_none() {
+                *nil_reason = Some(NilReason::NotReported);
+            }
+        }
+
+        fill(&self.wind.wind_from_direction, &mut self.wind.wind_from_direction_nil_reason);
+        fill(&self.wind.wind_speed, &mut self.wind.wind_speed_nil_reason);
+        fill(&self.visibility.prevailing_visibility, &mut self.visibility.prevailing_visibility_nil_reason);
+        fill(&self.temperature.temperature, &mut self.temperature.temperature_nil_reason);
+        fill(&self.temperature.dew_point, &mut self.temperature.dew_point_nil_reason);
+        fill(&self.pressure.pressure, &mut self.pressure.pressure_nil_reason);
+    }
+}
+
+/// Handles a group of a kind reported at most once, reached only if the kind was already decoded.
+///
+/// The group is a duplicate only if its kind is not before the `last_group` decoded in the canonical order,
+/// otherwise it is more likely an unrecognized group, e.g. a stray number `1013` after the pressure.
+fn handle_duplicate(section: GroupSection, last_group: Option<GroupKind>, text: &str) -> Option<(GroupKind, usize)> {
+    fn end<T>(handled: Result<Option<(T, usize)>, DecodeError>, group: GroupKind) -> Option<(GroupKind, usize)> {
+        handled.ok().flatten().map(|(_, relative_end)| (group, relative_end))
+    }
+
+    end(handle_wind(text), GroupKind::Wind)
+        .or_else(|| end(handle_visibility(text), GroupKind::Visibility))
+        .or_else(|| match section {
+            GroupSection::Main => end(handle_temperature(text), GroupKind::Temperature)
+                .or_else(|| end(handle_pressure(text), GroupKind::Pressure))
+                .or_else(|| end(handle_sea(text), GroupKind::Sea)),
+            GroupSection::Trend => None,
+        })
+        .filter(|(group, _)| !matches!(last_group, Some(last) if last > *group))
+}
+
+/// Options of the METAR decoding.
+///
+/// Example:
+/// ```
+/// use rweather_decoder::metar::{units::UnitSystem, AnchorPolicy, DecodeOptions};
+///
+/// let options = DecodeOptions::new()
+///     .anchor_policy(AnchorPolicy::PastOnly)
+///     .provenance(true)
+///     .units(UnitSystem::Metric);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Datetime that is ideally close to that one when the report was actually published.
+    /// If given, the decoded METAR day and time will be converted to a full datetime. See also [MetarTime::to_date_time()].
+    pub anchor_time: Option<NaiveDateTime>,
+    /// Policy of converting the observation time using the anchor time.
+    pub anchor_policy: AnchorPolicy,
+    /// Flag if the source spans of the decoded elements are recorded in [Metar::provenance].
+    pub provenance: bool,
+    /// Flag if the conformance with WMO FM 15 is checked, listing the violations in [Metar::violations].
+    pub strict: bool,
+    /// Flag if the decoding fails on the first invalid group, otherwise invalid groups are recorded
+    /// in [Diagnostics::invalid_groups] and skipped.
+    pub reject_invalid: bool,
+    /// Region of the national remark groups, [None] to select it by the station, see [RemarkRegion::from_station_id()].
+    pub region: Option<RemarkRegion>,
+    /// Flag if the remark groups are decoded, otherwise the remark section is kept only in [Remarks::raw].
+    pub remarks: bool,
+    /// Unit system of the decoded quantities, [None] to keep the units as reported. See [Metar::normalize_units()].
+    pub units: Option<UnitSystem>,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        DecodeOptions {
+            anchor_time: None,
+            anchor_policy: AnchorPolicy::default(),
+            provenance: false,
+            strict: false,
+            reject_invalid: false,
+            region: None,
+            remarks: true,
+            units: None,
+        }
+    }
+}
+
+impl DecodeOptions {
+    /// Creates the default options, i.e. no anchor time, no provenance, lenient decoding, invalid groups recorded
+    /// in the diagnostics, decoded remarks with the region of the station and units as reported.
+    pub fn new() -> DecodeOptions {
+        DecodeOptions::default()
+    }
+
+    /// Sets the anchor time.
+    pub fn anchor_time(mut self, anchor_time: NaiveDateTime) -> DecodeOptions {
+        self.anchor_time = Some(anchor_time);
+        self
+    }
+
+    /// Sets the anchor policy.
+    pub fn anchor_policy(mut self, anchor_policy: AnchorPolicy) -> DecodeOptions {
+        self.anchor_policy = anchor_policy;
+        self
+    }
+
+    /// Enables or disables recording of the source spans.
+    pub fn provenance(mut self, provenance: bool) -> DecodeOptions {
+        self.provenance = provenance;
+        self
+    }
+
+    /// Enables or disables the strict mode, see [conformance].
+    pub fn strict(mut self, strict: bool) -> DecodeOptions {
+        self.strict = strict;
+        self
+    }
+
+    /// Enables or disables rejecting the whole report on the first invalid group, see [error].
+    pub fn reject_invalid(mut self, reject_invalid: bool) -> DecodeOptions {
+        self.reject_invalid = reject_invalid;
+        self
+    }
+
+    /// Sets the region of the national remark groups regardless of the station.
+    pub fn region(mut self, region: RemarkRegion) -> DecodeOptions {
+        self.region = Some(region);
+        self
+    }
+
+    /// Enables or disables decoding of the remark groups.
+    pub fn remarks(mut self, remarks: bool) -> DecodeOptions {
+        self.remarks = remarks;
+        self
+    }
+
+    /// Sets the unit system of the decoded quantities, see [units].
+    pub fn units(mut self, units: UnitSystem) -> DecodeOptions {
+        self.units = Some(units);
+        self
+    }
+}
+
+/// Decoder of METAR reports with fixed [DecodeOptions].
+///
+/// Custom group handlers and remark decoders are configured per decoder, see [Decoder::group_handler()]
+/// and [Decoder::remark_decoder()].
+///
+/// Example:
+/// ```
+/// use rweather_decoder::metar::{DecodeOptions, Decoder};
+///
+/// let decoder = Decoder::new(DecodeOptions::new().strict(true));
+///
+/// for report in ["LKPR 121600Z 27010KT 9999 FEW030 14/08 Q1013", "LKPR 121630Z 27012KT 9999 FEW030 14/08 Q1013"] {
+///     let metar = decoder.decode(report).unwrap();
+///     assert_eq!(metar.violations, Some(Vec::new()));
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Decoder {
+    options: DecodeOptions,
+    group_handlers: Vec<RegisteredHandler>,
+    remark_decoders: Vec<Arc<dyn DynRemarkDecoder>>,
+}
+
+impl Decoder {
+    /// Creates a decoder with the given options and without any custom handlers and decoders.
+    pub fn new(options: DecodeOptions) -> Decoder {
+        Decoder { options, group_handlers: Vec::new(), remark_decoders: Vec::new() }
+    }
+
+    /// Adds a handler of custom groups in the given section, see [GroupHandler].
+    ///
+    /// The `placement` determines whether the handler is consulted before or after the built-in handlers.
+    /// Handlers with the same placement are consulted in the order of their `priority` from the highest,
+    /// handlers with the same priority in the order they are added.
+    ///
+    /// Example:
+    /// ```
+    /// use rweather_decoder::metar::{extension::{GroupHandler, GroupSection, HandlerPlacement}, DecodeOptions, Decoder};
+    ///
+    /// struct AwosHandler;
+    ///
+    /// impl GroupHandler for AwosHandler {
+    ///     type Item = String;
+    ///
+    ///     fn name(&self) -> &str {
+    ///         "awos"
+    ///     }
+    ///
+    ///     fn handle(&self, text: &str) -> Option<(Self::Item, usize)> {
+    ///         text.strip_prefix("AWOS")?.get(..2).filter(|g| g.ends_with(' ')).map(|g| (g[..1].to_string(), 6))
+    ///     }
+    /// }
+    ///
+    /// let decoder = Decoder::new(DecodeOptions::new()).group_handler(GroupSection::Main, HandlerPlacement::AfterBuiltIn, 0, AwosHandler);
+    /// let metar = decoder.decode("KXYZ 121600Z 27010KT 9999 AWOS3 FEW030 24/14 A2992").unwrap();
+    ///
+    /// assert_eq!(metar.extensions["awos"], vec![serde_json::json!("3")]);
+    /// ```
+    pub fn group_handler<H: GroupHandler + 'static>(mut self, section: GroupSection, placement: HandlerPlacement, priority: i32, handler: H) -> Decoder {
+        extension::insert_handler(&mut self.group_handlers, section, placement, priority, handler);
+        self
+    }
+
+    /// Adds a decoder of custom remark groups, see [RemarkDecoder].
+    ///
+    /// Decoders are consulted in the order they are added.
+    ///
+    /// Example:
+    /// ```
+    /// use rweather_decoder::metar::{remark::{Remark, RemarkDecoder}, DecodeOptions, Decoder};
+    ///
+    /// struct FlagDecoder;
+    ///
+    /// impl RemarkDecoder for FlagDecoder {
+    ///     type Item = bool;
+    ///
+    ///     fn name(&self) -> &str {
+    ///         "flag"
+    ///     }
+    ///
+    ///     fn decode(&self, text: &str) -> Option<(Self::Item, usize)> {
+    ///         text.starts_with("FLAG ").then_some((true, 5))
+    ///     }
+    /// }
+    ///
+    /// let decoder = Decoder::new(DecodeOptions::new()).remark_decoder(FlagDecoder);
+    /// let metar = decoder.decode("LKPR 121600Z 27010KT 9999 FEW030 14/08 Q1013 RMK FLAG").unwrap();
+    ///
+    /// assert!(matches!(&metar.remarks.items[..], [Remark::Custom { name, .. }] if name == "flag"));
+    /// ```
+    pub fn remark_decoder<D: RemarkDecoder + 'static>(mut self, decoder: D) -> Decoder {
+        self.remark_decoders.push(Arc::new(decoder));
+        self
+    }
+
+    /// Returns the options used for decoding every report.
+    pub fn options(&self) -> &DecodeOptions {
+        &self.options
+    }
+
+    /// Decodes a METAR report into a [Metar] struct.
+    ///
+    /// See [decode_metar()] for the errors.
+    pub fn decode(&self, report: &str) -> Result<Metar, DecodeError> {
+        let mut sanitized = report.to_uppercase().trim().replace('\x00', "");
+        sanitized = WHITESPACE_REPLACE_RE.replace_all(&sanitized, *WHITESPACE_REPLACE_OUT).to_string();
+        let report = END_REPLACE_RE.replace_all(&sanitized, *END_REPLACE_OUT).to_string();
+
+        // start of the group being decoded, handlers report errors relative to it
+        let mut group_start = 0;
+
+        decode_groups(&report, self, &mut group_start)
+            .map_err(|e| e.offset(group_start))
+    }
+}
+
+impl fmt::Debug for Decoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Decoder")
+            .field("options", &self.options)
+            .field("group_handlers", &self.group_handlers.len())
+            .field("remark_decoders", &self.remark_decoders.len())
+            .finish()
+    }
+}
+
 /// Decodes a METAR report into a [Metar] struct.
 ///
 /// # Arguments
 ///
 /// * `report` - METAR report to decode.
 /// * `anchor_time` - Specifies a datetime that is ideally close to that one when the report was actually published.
-///                   If given, the decoded METAR day and time will be converted to a full datetime. See also [MetarTime::to_date_time()].
-pub fn decode_metar(report: &str, anchor_time: Option<NaiveDateTime>) -> Result<Metar> {
-    let mut sanitized = report.to_uppercase().trim().replace('\x00', "");
-    sanitized = WHITESPACE_REPLACE_RE.replace_all(&sanitized, *WHITESPACE_REPLACE_OUT).to_string();
-    let report = END_REPLACE_RE.replace_all(&sanitized, *END_REPLACE_OUT).to_string();
+///   If given, the decoded METAR day and time will be converted to the nearest full datetime. See also [MetarTime::to_date_time()].
+///
+/// Groups which are recognized but their content is invalid are recorded in [Diagnostics::invalid_groups] and skipped.
+///
+/// Only the built-in groups are decoded, custom remark decoders and group handlers are used only by the [Decoder]
+/// they are added to.
+///
+/// # Errors
+///
+/// Returns [DecodeError] if a group is invalid and [DecodeOptions::reject_invalid] is set, never with the default options.
+/// The error contains the byte range of the group in the sanitized report, see [Metar::report].
+pub fn decode_metar(report: &str, anchor_time: Option<NaiveDateTime>) -> Result<Metar, DecodeError> {
+    let options = DecodeOptions { anchor_time, ..Default::default() };
 
+    decode_metar_with_options(report, &options)
+}
+
+/// Decodes a METAR report into a [Metar] struct using the given [DecodeOptions].
+///
+/// Use [Decoder] to decode many reports with the same options. See [decode_metar()] for the errors.
+pub fn decode_metar_with_options(report: &str, options: &DecodeOptions) -> Result<Metar, DecodeError> {
+    Decoder::new(*options).decode(report)
+}
+
+fn decode_groups(report: &str, decoder: &Decoder, group_start: &mut usize) -> Result<Metar, DecodeError> {
+    let options = &decoder.options;
     let mut section = Section::Main;
 
-    let mut metar = Metar::default();
-    metar.report = report.trim().to_string();
+    let mut metar = Metar {
+        // the conformance check locates the violations using the provenance
+        provenance: (options.provenance || options.strict).then(Provenance::default),
+        report: report.trim().to_string(),
+        ..Default::default()
+    };
 
     let mut processing_trend_change = false;
     let mut trend_change = TrendChange::default();
 
-    let mut unparsed_groups = Vec::new();
+    let mut remark_start = None;
 
     // Handlers return mostly `Option<(some struct, end index)>` which gives us:
     // - None => the handler didn't parse the group which often leads to trying an another handler
     // - Some(some struct, end index) => the handler parsed the group (to some struct) and also returned an index of the group end
     //                                   which enables to further slice the report for other handlers to work with
     //
-    // In certain cases, some struct may be empty (determined by `.is_empty()`) because all of its fields are missing.
-    // For example, this typically happens when clouds are unknown (//////) and such empty struct will be skipped.
+    // Handlers of groups with a fallible content return `Result<Option<...>, DecodeError>` instead, an error means
+    // that the group was recognized but its content is invalid, e.g. an observation time `122575Z`.
+    // Such group is recorded in the diagnostics and skipped, unless invalid groups are rejected.
+    //
+    // In certain cases, some struct may be empty (determined by `.is_empty()`) because all of its values are missing.
+    // For example, this typically happens when temperatures are unknown (/////). Such struct is still stored
+    // to keep the nil reasons, but a later group of the same kind may replace it.
 
     let mut idx = 0;
 
+    macro_rules! recover {
+        // records the invalid group and continues with the recovered result
+        ($handled:expr, $recovered:expr) => {
+            match $handled {
+                Ok(decoded) => decoded,
+                Err(e) if options.reject_invalid => return Err(e),
+                Err(e) => {
+                    metar.diagnostics.invalid_groups.push(e.offset(idx));
+                    $recovered
+                },
+            }
+        };
+        // records the invalid group and skips it including the whitespace that ends it
+        ($handled:expr) => {
+            match $handled {
+                Ok(decoded) => decoded,
+                Err(e) if options.reject_invalid => return Err(e),
+                Err(e) => {
+                    let e = e.offset(idx);
+                    idx = (e.span().end + 1).min(report.len());
+                    metar.diagnostics.invalid_groups.push(e);
+                    continue;
+                },
+            }
+        };
+    }
+
     while idx < report.len() {
         let sub_report = &report[idx..];
+        *group_start = idx;
 
         if let Some((sec, relative_end)) = handle_section(sub_report) {
+            if let Some(start) = remark_start.take() {
+                metar.remarks.push_raw(&report[start..idx]);
+                metar.record_span(format_args!("remarks.raw"), start..idx);
+            }
+
             section = sec;
             idx += relative_end;
 
+            if section == Section::Remark {
+                remark_start = Some(idx);
+            }
+
             if processing_trend_change {
                 metar.trend_changes.push(trend_change.clone());
                 processing_trend_change = false;
@@ -1528,36 +2382,65 @@ pub fn decode_metar(report: &str, anchor_time: Option<NaiveDateTime>) -> Result<
             if let Section::Trend(trend) = section {
                 processing_trend_change = true;
                 trend_change.indicator = trend;
+                metar.record_span(format_args!("trend_changes[{}].indicator", metar.trend_changes.len()), idx - relative_end..idx);
             }
 
             continue;
         }
 
+        let group_section = match section {
+            Section::Main => Some(GroupSection::Main),
+            Section::Trend(_) => Some(GroupSection::Trend),
+            Section::Remark => None,
+        };
+
+        if let Some(gs) = group_section {
+            if let Some((name, value, relative_end)) = extension::handle_group(&decoder.group_handlers, gs, HandlerPlacement::BeforeBuiltIn, sub_report) {
+                metar.record_span(format_args!("extensions.{}[{}]", name, metar.extensions.get(&name).map_or(0, Vec::len)), idx..idx + relative_end);
+                metar.extensions.entry(name).or_default().push(value);
+                idx += relative_end;
+                continue;
+            }
+        }
+
         match section {
             Section::Main => {
                 if metar.header.is_empty() {
-                    if let Some((header, relative_end)) = handle_header(sub_report, anchor_time) {
+                    if let Some((header, relative_end)) = recover!(handle_header(sub_report, options.anchor_time, options.anchor_policy), handle_header_without_time(sub_report)) {
                         metar.header = header;
+                        metar.record_span(format_args!("header"), idx..idx + relative_end);
+                        metar.diagnostics.push_group(GroupKind::Header, idx..idx + relative_end, report);
                         idx += relative_end;
                         continue;
                     }
                 }
 
                 if metar.wind.is_empty() {
-                    if let Some((wind, relative_end)) = handle_wind(sub_report) {
+                    if let Some((wind, relative_end)) = recover!(handle_wind(sub_report)) {
                         metar.wind = wind;
+                        metar.record_span(format_args!("wind"), idx..idx + relative_end);
+
+                        // groups with all values missing are ambiguous, e.g. `/////`, and do not affect the order
+                        if !metar.wind.is_empty() {
+                            metar.diagnostics.push_group(GroupKind::Wind, idx..idx + relative_end, report);
+                        }
+
+                        if diagnostics::is_suspicious_wind(&metar.wind) {
+                            metar.diagnostics.push_warning(WarningKind::SuspiciousValue, GroupKind::Wind, idx..idx + relative_end, report);
+                        }
+
                         idx += relative_end;
                         continue;
                     }
                 }
 
                 if metar.visibility.is_empty() {
-                    if let Some((visibility, is_cavok, relative_end)) = handle_visibility(sub_report) {
+                    if let Some((visibility, relative_end)) = recover!(handle_visibility(sub_report)) {
                         metar.visibility = visibility;
+                        metar.record_span(format_args!("visibility"), idx..idx + relative_end);
 
-                        if is_cavok {
-                            let cloud_layer = CloudLayer { cover: Some(CloudCover::CeilingOk) , height: None, cloud_type: None };
-                            metar.clouds.push(cloud_layer);
+                        if !metar.visibility.is_empty() {
+                            metar.diagnostics.push_group(GroupKind::Visibility, idx..idx + relative_end, report);
                         }
 
                         idx += relative_end;
@@ -1567,29 +2450,39 @@ pub fn decode_metar(report: &str, anchor_time: Option<NaiveDateTime>) -> Result<
 
                 if let Some((weather_condition, relative_end)) = handle_present_weather(sub_report) {
                     metar.present_weather.push(weather_condition);
+                    metar.record_span(format_args!("present_weather[{}]", metar.present_weather.len() - 1), idx..idx + relative_end);
+                    metar.diagnostics.push_group(GroupKind::PresentWeather, idx..idx + relative_end, report);
                     idx += relative_end;
                     continue;
                 }
 
-                if let Some((runway_visual_range, relative_end)) = handle_runway_visual_range(sub_report) {
+                if let Some((runway_visual_range, relative_end)) = recover!(handle_runway_visual_range(sub_report)) {
                     metar.runway_visual_ranges.push(runway_visual_range);
+                    metar.record_span(format_args!("runway_visual_ranges[{}]", metar.runway_visual_ranges.len() - 1), idx..idx + relative_end);
+                    metar.diagnostics.push_group(GroupKind::RunwayVisualRange, idx..idx + relative_end, report);
                     idx += relative_end;
                     continue;
                 }
 
-                if let Some((cloud_layer, relative_end)) = handle_cloud_layer(sub_report) {
-                    if !cloud_layer.is_empty() {
-                        metar.clouds.push(cloud_layer);
-                    }
-
+                if let Some((cloud_layer, relative_end)) = recover!(handle_cloud_layer(sub_report)) {
+                    metar.clouds.push(cloud_layer);
+                    metar.record_span(format_args!("clouds[{}]", metar.clouds.len() - 1), idx..idx + relative_end);
+                    metar.diagnostics.push_group(GroupKind::Cloud, idx..idx + relative_end, report);
                     idx += relative_end;
                     continue;
                 }
 
                 if metar.temperature.is_empty() {
-                    if let Some((temperature, relative_end)) = handle_temperature(sub_report) {
-                        if !temperature.is_empty() {
-                            metar.temperature = temperature;
+                    if let Some((temperature, relative_end)) = recover!(handle_temperature(sub_report)) {
+                        metar.temperature = temperature;
+                        metar.record_span(format_args!("temperature"), idx..idx + relative_end);
+
+                        if !metar.temperature.is_empty() {
+                            metar.diagnostics.push_group(GroupKind::Temperature, idx..idx + relative_end, report);
+                        }
+
+                        if diagnostics::is_suspicious_temperature(&metar.temperature) {
+                            metar.diagnostics.push_warning(WarningKind::SuspiciousValue, GroupKind::Temperature, idx..idx + relative_end, report);
                         }
 
                         idx += relative_end;
@@ -1598,8 +2491,18 @@ pub fn decode_metar(report: &str, anchor_time: Option<NaiveDateTime>) -> Result<
                 }
 
                 if metar.pressure.is_empty() {
-                    if let Some((pressure, relative_end)) = handle_pressure(sub_report) {
+                    if let Some((pressure, relative_end)) = recover!(handle_pressure(sub_report)) {
                         metar.pressure = pressure;
+                        metar.record_span(format_args!("pressure"), idx..idx + relative_end);
+
+                        if !metar.pressure.is_empty() {
+                            metar.diagnostics.push_group(GroupKind::Pressure, idx..idx + relative_end, report);
+                        }
+
+                        if diagnostics::is_suspicious_pressure(&metar.pressure) {
+                            metar.diagnostics.push_warning(WarningKind::SuspiciousValue, GroupKind::Pressure, idx..idx + relative_end, report);
+                        }
+
                         idx += relative_end;
                         continue;
                     }
@@ -1607,20 +2510,27 @@ pub fn decode_metar(report: &str, anchor_time: Option<NaiveDateTime>) -> Result<
 
                 if let Some((weather_condition, relative_end)) = handle_recent_weather(sub_report) {
                     metar.recent_weather.push(weather_condition);
+                    metar.record_span(format_args!("recent_weather[{}]", metar.recent_weather.len() - 1), idx..idx + relative_end);
+                    metar.diagnostics.push_group(GroupKind::RecentWeather, idx..idx + relative_end, report);
                     idx += relative_end;
                     continue;
                 }
 
-                if let Some((wind_shear, relative_end)) = handle_wind_shear(sub_report) {
+                if let Some((wind_shear, relative_end)) = recover!(handle_wind_shear(sub_report)) {
                     metar.wind_shears.push(wind_shear);
+                    metar.record_span(format_args!("wind_shears[{}]", metar.wind_shears.len() - 1), idx..idx + relative_end);
+                    metar.diagnostics.push_group(GroupKind::WindShear, idx..idx + relative_end, report);
                     idx += relative_end;
                     continue;
                 }
 
                 if metar.sea.is_empty() {
-                    if let Some((sea, relative_end)) = handle_sea(sub_report) {
-                        if !sea.is_empty() {
-                            metar.sea = sea;
+                    if let Some((sea, relative_end)) = recover!(handle_sea(sub_report)) {
+                        metar.sea = sea;
+                        metar.record_span(format_args!("sea"), idx..idx + relative_end);
+
+                        if !metar.sea.is_empty() {
+                            metar.diagnostics.push_group(GroupKind::Sea, idx..idx + relative_end, report);
                         }
 
                         idx += relative_end;
@@ -1650,40 +2560,41 @@ pub fn decode_metar(report: &str, anchor_time: Option<NaiveDateTime>) -> Result<
                 }
             },
             Section::Trend(_) => {
-                if let Some((trend_time, relative_end)) = handle_trend_time(sub_report, anchor_time) {
-                    match trend_time.indicator {
+                if let Some((trend_time, relative_end)) = recover!(handle_trend_time(sub_report, options.anchor_time), handle_trend_time_without_time(sub_report)) {
+                    let field = match trend_time.indicator {
                         TrendTimeIndicator::From => {
                             trend_change.from_time = trend_time.time;
+                            "from_time"
                         },
                         TrendTimeIndicator::Until => {
                             trend_change.to_time = trend_time.time;
+                            "to_time"
                         },
                         TrendTimeIndicator::At => {
                             trend_change.at_time = trend_time.time;
+                            "at_time"
                         },
-                    }
+                    };
+
+                    metar.record_span(format_args!("trend_changes[{}].{}", metar.trend_changes.len(), field), idx..idx + relative_end);
 
                     idx += relative_end;
                     continue;
                 }
 
                 if trend_change.wind.is_empty() {
-                    if let Some((wind, relative_end)) = handle_wind(sub_report) {
+                    if let Some((wind, relative_end)) = recover!(handle_wind(sub_report)) {
                         trend_change.wind = wind;
+                        metar.record_span(format_args!("trend_changes[{}].wind", metar.trend_changes.len()), idx..idx + relative_end);
                         idx += relative_end;
                         continue;
                     }
                 }
 
                 if trend_change.visibility.is_empty() {
-                    if let Some((visibility, is_cavok, relative_end)) = handle_visibility(sub_report) {
+                    if let Some((visibility, relative_end)) = recover!(handle_visibility(sub_report)) {
                         trend_change.visibility = visibility;
-
-                        if is_cavok {
-                            let cloud_layer = CloudLayer { cover: Some(CloudCover::CeilingOk) , height: None, cloud_type: None };
-                            trend_change.clouds.push(cloud_layer);
-                        }
-
+                        metar.record_span(format_args!("trend_changes[{}].visibility", metar.trend_changes.len()), idx..idx + relative_end);
                         idx += relative_end;
                         continue;
                     }
@@ -1691,27 +2602,64 @@ pub fn decode_metar(report: &str, anchor_time: Option<NaiveDateTime>) -> Result<
 
                 if let Some((weather_condition, relative_end)) = handle_present_weather(sub_report) {
                     trend_change.weather.push(weather_condition);
+                    metar.record_span(format_args!("trend_changes[{}].weather[{}]", metar.trend_changes.len(), trend_change.weather.len() - 1), idx..idx + relative_end);
                     idx += relative_end;
                     continue;
                 }
 
-                if let Some((cloud_layer, relative_end)) = handle_cloud_layer(sub_report) {
-                    if !cloud_layer.is_empty() {
-                        trend_change.clouds.push(cloud_layer);
-                    }
+                if let Some((cloud_layer, relative_end)) = recover!(handle_cloud_layer(sub_report)) {
+                    trend_change.clouds.push(cloud_layer);
+                    metar.record_span(format_args!("trend_changes[{}].clouds[{}]", metar.trend_changes.len(), trend_change.clouds.len() - 1), idx..idx + relative_end);
+                    idx += relative_end;
+                    continue;
+                }
+            },
+            Section::Remark if options.remarks => {
+                let remark_region = options.region.or_else(|| metar.header.station_id.as_deref().and_then(RemarkRegion::from_station_id));
 
+                if let Some(relative_end) = remark::handle_built_in(&mut metar, decoder, remark_region, sub_report, idx) {
                     idx += relative_end;
                     continue;
                 }
             },
-            Section::Remark => (), // TODO: https://github.com/meandair/rweather-decoder/issues/15
+            // remark groups are kept only in the raw text
+            Section::Remark => {},
+        }
+
+        if let Some(gs) = group_section {
+            if let Some((name, value, relative_end)) = extension::handle_group(&decoder.group_handlers, gs, HandlerPlacement::AfterBuiltIn, sub_report) {
+                metar.record_span(format_args!("extensions.{}[{}]", name, metar.extensions.get(&name).map_or(0, Vec::len)), idx..idx + relative_end);
+                metar.extensions.entry(name).or_default().push(value);
+                idx += relative_end;
+                continue;
+            }
+        }
+
+        if let Some(gs) = group_section {
+            let last_group = match gs {
+                GroupSection::Main => metar.diagnostics.last_group(),
+                GroupSection::Trend => trend_change.last_group(),
+            };
+
+            if let Some((group, relative_end)) = handle_duplicate(gs, last_group, sub_report) {
+                metar.diagnostics.push_warning(WarningKind::Duplicate, group, idx..idx + relative_end, report);
+                idx += relative_end;
+                continue;
+            }
         }
 
         let relative_end = sub_report.find(' ').unwrap();
 
         let unparsed = &report[idx..idx + relative_end];
         if unparsed.chars().any(|c| c != '/') {
-            unparsed_groups.push(unparsed);
+            if section == Section::Remark {
+                if options.remarks {
+                    metar.remarks.unparsed.push(unparsed.to_string());
+                }
+            } else {
+                metar.diagnostics.unparsed_groups.push(unparsed.to_string());
+                metar.record_span(format_args!("diagnostics.unparsed_groups[{}]", metar.diagnostics.unparsed_groups.len() - 1), idx..idx + relative_end);
+            }
         }
 
         idx += relative_end + 1;
@@ -1721,8 +2669,29 @@ pub fn decode_metar(report: &str, anchor_time: Option<NaiveDateTime>) -> Result<
         metar.trend_changes.push(trend_change);
     }
 
-    if !unparsed_groups.is_empty() {
-        log::debug!("Unparsed data: {}, report: {}", unparsed_groups.join(" "), report);
+    if let Some(start) = remark_start {
+        metar.remarks.push_raw(&report[start..]);
+        metar.record_span(format_args!("remarks.raw"), start..report.len());
+    }
+
+    metar.fill_not_reported();
+
+    if options.strict {
+        let provenance = metar.provenance.take().unwrap_or_default();
+        metar.violations = Some(conformance::check_conformance(&metar, &provenance));
+
+        if options.provenance {
+            metar.provenance = Some(provenance);
+        }
+    }
+
+    // the conformance is checked in the reported units
+    if let Some(units) = options.units {
+        metar.normalize_units(units);
+    }
+
+    if !metar.diagnostics.unparsed_groups.is_empty() {
+        log::debug!("Unparsed data: {}, report: {}", metar.diagnostics.unparsed_groups.join(" "), report);
     }
 
     Ok(metar)
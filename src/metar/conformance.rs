@@ -0,0 +1,286 @@
+//! Module for checking the conformance of METAR reports with WMO FM 15.
+//!
+//! The decoding is lenient, i.e. it accepts groups in any order and skips unrecognized groups.
+//! The strict mode, enabled by [DecodeOptions::strict()](super::DecodeOptions::strict), lists the conformance violations instead,
+//! e.g. for validating outgoing reports. The checks are based on the following publications:
+//! - World Meteorological Organization (2019). Manual on Codes, Volume I.1 – International Codes, FM 15 METAR. Available: <https://library.wmo.int/idurl/4/35713>.
+//! - International Civil Aviation Organization (2018). Annex 3 – Meteorological Service for International Air Navigation, Appendix 3.
+
+use std::ops::Range;
+
+use serde::{Serialize, Deserialize};
+
+use super::{
+    diagnostics::{GroupKind, WarningKind},
+    error::DecodeError,
+    provenance::Provenance,
+    CloudCover, CloudLayer, Metar, Quantity, TrendChange, Unit, Value, Visibility, Wind,
+};
+
+/// Cloud heights above this height in feet are reported in steps of 1000 ft instead of 100 ft.
+const CLOUD_HEIGHT_STEP_LIMIT: f64 = 10000.0;
+
+/// Maximum number of present weather groups.
+const MAX_PRESENT_WEATHER: usize = 3;
+
+/// Maximum number of RVR groups.
+const MAX_RUNWAY_VISUAL_RANGES: usize = 4;
+
+/// Kind of a conformance violation.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationKind {
+    /// Group reported out of the canonical order.
+    GroupOrder,
+    /// Group of a kind reported at most once which was reported repeatedly.
+    DuplicateGroup,
+    /// Group not recognized in the main or TREND section.
+    UnrecognizedGroup,
+    /// Group recognized but with an invalid content, e.g. an observation time `122575Z`.
+    InvalidGroup,
+    /// Mandatory group not included in the report.
+    MissingGroup,
+    /// Wind direction not rounded to 10 degrees.
+    WindDirectionStep,
+    /// Visibility not rounded to the reporting steps, i.e. 50 m below 800 m, 100 m below 5 km and 1000 m below 10 km.
+    VisibilityStep,
+    /// Cloud height not rounded to the reporting steps, i.e. 100 ft up to 10000 ft and 1000 ft above.
+    CloudHeightStep,
+    /// Groups which must not be reported together, e.g. CAVOK with present weather.
+    CodeCombination,
+    /// More groups of a kind than allowed, e.g. four present weather groups.
+    GroupCount,
+}
+
+/// Conformance violation.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Violation {
+    pub kind: ViolationKind,
+    /// Kind of the offending group, [None] for unrecognized groups and TREND time groups.
+    pub group: Option<GroupKind>,
+    /// Byte range of the offending group in the sanitized report, [None] for missing groups.
+    pub span: Option<Range<usize>>,
+}
+
+struct Checker<'a> {
+    provenance: &'a Provenance,
+    violations: Vec<Violation>,
+}
+
+impl Checker<'_> {
+    fn push(&mut self, kind: ViolationKind, group: Option<GroupKind>, path: &str) {
+        let span = self.provenance.get(path).cloned();
+        self.violations.push(Violation { kind, group, span });
+    }
+}
+
+/// Returns the reported numbers of a quantity, e.g. both extreme directions of a variable wind.
+fn numbers(quantity: Option<&Quantity>) -> Vec<f64> {
+    match quantity.map(|q| q.value) {
+        Some(Value::Exact(x) | Value::Above(x) | Value::Below(x)) => vec![x.as_f64()],
+        Some(Value::Range(first, second)) => vec![first.as_f64(), second.as_f64()],
+        _ => Vec::new(),
+    }
+}
+
+fn is_wind_direction_conforming(direction: Option<&Quantity>) -> bool {
+    numbers(direction).into_iter().all(|d| d % 10.0 == 0.0)
+}
+
+/// Checks the wind direction and the extreme directions of a variable wind.
+fn is_wind_conforming(wind: &Wind) -> bool {
+    is_wind_direction_conforming(wind.wind_from_direction.as_ref())
+        && is_wind_direction_conforming(wind.wind_from_direction_range.as_ref())
+}
+
+fn is_visibility_conforming(visibility: Option<&Quantity>) -> bool {
+    if visibility.is_some_and(|v| v.units != Unit::Metre) {
+        return true;
+    }
+
+    let step = |v: f64| match v {
+        v if v < 800.0 => 50.0,
+        v if v < 5000.0 => 100.0,
+        v if v < 10000.0 => 1000.0,
+        _ => 1.0,
+    };
+
+    numbers(visibility).into_iter().all(|v| v % step(v) == 0.0)
+}
+
+/// Checks the prevailing, minimum and directional visibilities.
+fn is_visibility_group_conforming(visibility: &Visibility) -> bool {
+    is_visibility_conforming(visibility.prevailing_visibility.as_ref())
+        && is_visibility_conforming(visibility.minimum_visibility.as_ref())
+        && visibility.directional_visibilites.iter().all(|dv| is_visibility_conforming(Some(&dv.visibility)))
+}
+
+fn is_cloud_height_conforming(height: Option<&Quantity>) -> bool {
+    let step = |h: f64| if h <= CLOUD_HEIGHT_STEP_LIMIT { 100.0 } else { 1000.0 };
+
+    numbers(height).into_iter().all(|h| h % step(h) == 0.0)
+}
+
+/// Checks the order of the groups of a TREND change, i.e. time groups, wind, visibility, weather and clouds.
+fn check_trend_order(checker: &mut Checker, trend_change: &TrendChange, i: usize) {
+    let mut groups: Vec<(usize, Option<GroupKind>, String)> = vec![
+        (0, None, format!("trend_changes[{}].from_time", i)),
+        (0, None, format!("trend_changes[{}].to_time", i)),
+        (0, None, format!("trend_changes[{}].at_time", i)),
+        (1, Some(GroupKind::Wind), format!("trend_changes[{}].wind", i)),
+        (2, Some(GroupKind::Visibility), format!("trend_changes[{}].visibility", i)),
+    ];
+
+    groups.extend((0..trend_change.weather.len()).map(|j| (3, Some(GroupKind::PresentWeather), format!("trend_changes[{}].weather[{}]", i, j))));
+    groups.extend((0..trend_change.clouds.len()).map(|j| (4, Some(GroupKind::Cloud), format!("trend_changes[{}].clouds[{}]", i, j))));
+
+    let mut spans: Vec<_> = groups.into_iter()
+        .filter_map(|(rank, group, path)| checker.provenance.get(&path).map(|span| (span.clone(), rank, group)))
+        .collect();
+
+    spans.sort_by_key(|(span, _, _)| span.start);
+
+    let mut last_rank = 0;
+
+    for (span, rank, group) in spans {
+        if rank < last_rank {
+            checker.violations.push(Violation { kind: ViolationKind::GroupOrder, group, span: Some(span) });
+        } else {
+            last_rank = rank;
+        }
+    }
+}
+
+/// Checks the conformance of a decoded report using the spans of its groups.
+pub(super) fn check_conformance(metar: &Metar, provenance: &Provenance) -> Vec<Violation> {
+    let mut checker = Checker { provenance, violations: Vec::new() };
+
+    for warning in metar.diagnostics.warnings.iter() {
+        let kind = match warning.kind {
+            WarningKind::OutOfOrder => ViolationKind::GroupOrder,
+            WarningKind::Duplicate => ViolationKind::DuplicateGroup,
+            _ => continue,
+        };
+
+        checker.violations.push(Violation { kind, group: Some(warning.group), span: Some(warning.span.clone()) });
+    }
+
+    for i in 0..metar.diagnostics.unparsed_groups.len() {
+        checker.push(ViolationKind::UnrecognizedGroup, None, &format!("diagnostics.unparsed_groups[{}]", i));
+    }
+
+    for error in metar.diagnostics.invalid_groups.iter() {
+        let group = match error {
+            DecodeError::Header(_) => Some(GroupKind::Header),
+            DecodeError::Wind(_) => Some(GroupKind::Wind),
+            DecodeError::Visibility(_) => Some(GroupKind::Visibility),
+            DecodeError::RunwayVisualRange(_) => Some(GroupKind::RunwayVisualRange),
+            DecodeError::Cloud(_) => Some(GroupKind::Cloud),
+            DecodeError::Temperature(_) => Some(GroupKind::Temperature),
+            DecodeError::Pressure(_) => Some(GroupKind::Pressure),
+            DecodeError::WindShear(_) => Some(GroupKind::WindShear),
+            DecodeError::Sea(_) => Some(GroupKind::Sea),
+            DecodeError::TrendTime(_) => None,
+        };
+
+        checker.violations.push(Violation { kind: ViolationKind::InvalidGroup, group, span: Some(error.span()) });
+    }
+
+    // mandatory groups
+    let mandatory = [
+        ("header", GroupKind::Header),
+        ("wind", GroupKind::Wind),
+        ("visibility", GroupKind::Visibility),
+        ("temperature", GroupKind::Temperature),
+        ("pressure", GroupKind::Pressure),
+    ];
+
+    for (path, group) in mandatory {
+        if provenance.get(path).is_none() {
+            checker.push(ViolationKind::MissingGroup, Some(group), path);
+        }
+    }
+
+    // clouds are replaced by CAVOK, cloud layers reported only by solidi (`//////`) do not count
+    if !metar.visibility.is_cavok && metar.clouds.iter().all(CloudLayer::is_empty) {
+        checker.push(ViolationKind::MissingGroup, Some(GroupKind::Cloud), "clouds[0]");
+    }
+
+    for (i, trend_change) in metar.trend_changes.iter().enumerate() {
+        check_trend_order(&mut checker, trend_change, i);
+    }
+
+    // value steps
+    if !is_wind_conforming(&metar.wind) {
+        checker.push(ViolationKind::WindDirectionStep, Some(GroupKind::Wind), "wind");
+    }
+
+    let visibility = &metar.visibility;
+
+    if !is_visibility_group_conforming(visibility) {
+        checker.push(ViolationKind::VisibilityStep, Some(GroupKind::Visibility), "visibility");
+    }
+
+    for (i, cloud) in metar.clouds.iter().enumerate() {
+        if !is_cloud_height_conforming(cloud.height.as_ref()) {
+            checker.push(ViolationKind::CloudHeightStep, Some(GroupKind::Cloud), &format!("clouds[{}]", i));
+        }
+    }
+
+    for (i, trend_change) in metar.trend_changes.iter().enumerate() {
+        if !is_wind_conforming(&trend_change.wind) {
+            checker.push(ViolationKind::WindDirectionStep, Some(GroupKind::Wind), &format!("trend_changes[{}].wind", i));
+        }
+
+        if !is_visibility_group_conforming(&trend_change.visibility) {
+            checker.push(ViolationKind::VisibilityStep, Some(GroupKind::Visibility), &format!("trend_changes[{}].visibility", i));
+        }
+
+        for (j, cloud) in trend_change.clouds.iter().enumerate() {
+            if !is_cloud_height_conforming(cloud.height.as_ref()) {
+                checker.push(ViolationKind::CloudHeightStep, Some(GroupKind::Cloud), &format!("trend_changes[{}].clouds[{}]", i, j));
+            }
+        }
+    }
+
+    // code combinations, CAVOK replaces the visibility, RVR, present weather and cloud groups
+    if visibility.is_cavok {
+        for i in 0..metar.runway_visual_ranges.len() {
+            checker.push(ViolationKind::CodeCombination, Some(GroupKind::RunwayVisualRange), &format!("runway_visual_ranges[{}]", i));
+        }
+
+        for i in 0..metar.present_weather.len() {
+            checker.push(ViolationKind::CodeCombination, Some(GroupKind::PresentWeather), &format!("present_weather[{}]", i));
+        }
+
+        for i in 0..metar.clouds.len() {
+            checker.push(ViolationKind::CodeCombination, Some(GroupKind::Cloud), &format!("clouds[{}]", i));
+        }
+    } else if metar.clouds.len() > 1 {
+        // no cloud and vertical visibility groups are reported alone
+        for (i, cloud) in metar.clouds.iter().enumerate() {
+            let is_alone = matches!(cloud.cover, Some(
+                CloudCover::Clear | CloudCover::SkyClear | CloudCover::NilSignificantCloud | CloudCover::NoCloudDetected | CloudCover::VerticalVisibility
+            ));
+
+            if is_alone {
+                checker.push(ViolationKind::CodeCombination, Some(GroupKind::Cloud), &format!("clouds[{}]", i));
+            }
+        }
+    }
+
+    // group counts
+    if metar.present_weather.len() > MAX_PRESENT_WEATHER {
+        checker.push(ViolationKind::GroupCount, Some(GroupKind::PresentWeather), &format!("present_weather[{}]", MAX_PRESENT_WEATHER));
+    }
+
+    if metar.runway_visual_ranges.len() > MAX_RUNWAY_VISUAL_RANGES {
+        checker.push(ViolationKind::GroupCount, Some(GroupKind::RunwayVisualRange), &format!("runway_visual_ranges[{}]", MAX_RUNWAY_VISUAL_RANGES));
+    }
+
+    checker.violations
+}
@@ -0,0 +1,132 @@
+//! Module for diagnostics of the METAR decoding.
+//!
+//! Diagnostics quantify the quality of the decoding, e.g. for the quality control of ingested reports.
+//! They are collected for the main and TREND sections, undecoded remark groups are kept in [Remarks::unparsed](super::remark::Remarks::unparsed).
+
+use std::ops::Range;
+
+use serde::{Serialize, Deserialize};
+
+use super::{error::DecodeError, Pressure, Temperature, Unit, Value, Wind};
+
+/// Kind of a group in the main section, ordered by the canonical order of WMO FM 15.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupKind {
+    Header,
+    Wind,
+    Visibility,
+    RunwayVisualRange,
+    PresentWeather,
+    Cloud,
+    Temperature,
+    Pressure,
+    RecentWeather,
+    WindShear,
+    Sea,
+}
+
+/// Kind of a decoding warning.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// Group reported after a group which follows it in the canonical order, e.g. the temperature before the clouds.
+    OutOfOrder,
+    /// Group of a kind reported at most once which was already decoded, the group is ignored.
+    Duplicate,
+    /// Physically implausible value, e.g. a dew point above the temperature or a wind gust not above the wind speed.
+    SuspiciousValue,
+}
+
+/// Decoding warning.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Warning {
+    pub kind: WarningKind,
+    pub group: GroupKind,
+    /// Byte range of the group in the sanitized report, see [Metar::report](super::Metar::report).
+    pub span: Range<usize>,
+    pub token: String,
+}
+
+/// Diagnostics of the decoding.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    /// Groups of the main and TREND sections which were not decoded.
+    pub unparsed_groups: Vec<String>,
+    /// Groups of the main and TREND sections which were recognized but their content is invalid, the groups are skipped.
+    pub invalid_groups: Vec<DecodeError>,
+    pub warnings: Vec<Warning>,
+    /// Kind of the last decoded group of the main section.
+    #[serde(skip)]
+    last_group: Option<GroupKind>,
+}
+
+impl Diagnostics {
+    /// Returns `true` if all groups were decoded without warnings.
+    pub fn is_clean(&self) -> bool {
+        self.unparsed_groups.is_empty() && self.invalid_groups.is_empty() && self.warnings.is_empty()
+    }
+
+    pub(super) fn push_warning(&mut self, kind: WarningKind, group: GroupKind, span: Range<usize>, report: &str) {
+        let token = report[span.clone()].trim_end();
+        let span = span.start..span.start + token.len();
+
+        self.warnings.push(Warning { kind, group, span, token: token.to_string() });
+    }
+
+    /// Returns the kind of the last decoded group of the main section in the canonical order.
+    pub(super) fn last_group(&self) -> Option<GroupKind> {
+        self.last_group
+    }
+
+    /// Records a decoded group of the main section, warns if it is out of the canonical order.
+    pub(super) fn push_group(&mut self, group: GroupKind, span: Range<usize>, report: &str) {
+        if self.last_group.is_some_and(|last| last > group) {
+            self.push_warning(WarningKind::OutOfOrder, group, span, report);
+        } else {
+            self.last_group = Some(group);
+        }
+    }
+}
+
+fn exact(value: &Value) -> Option<f64> {
+    match value {
+        Value::Exact(x) => Some(x.as_f64()),
+        _ => None,
+    }
+}
+
+/// Checks that the wind direction is within 360° and the gust is above the wind speed.
+pub(super) fn is_suspicious_wind(wind: &Wind) -> bool {
+    let direction = wind.wind_from_direction.and_then(|q| exact(&q.value));
+    let speed = wind.wind_speed.and_then(|q| exact(&q.value));
+    let gust = wind.wind_gust.and_then(|q| exact(&q.value));
+
+    direction.is_some_and(|d| d > 360.0) || matches!((speed, gust), (Some(s), Some(g)) if g <= s)
+}
+
+/// Checks that the temperature is within the range of observed temperatures and the dew point is not above it.
+pub(super) fn is_suspicious_temperature(temperature: &Temperature) -> bool {
+    let t = temperature.temperature.and_then(|q| exact(&q.value));
+    let td = temperature.dew_point.and_then(|q| exact(&q.value));
+
+    t.is_some_and(|t| !(-90.0..=60.0).contains(&t)) || matches!((t, td), (Some(t), Some(td)) if td > t)
+}
+
+/// Checks that the pressure is within the range of observed sea-level pressures.
+pub(super) fn is_suspicious_pressure(pressure: &Pressure) -> bool {
+    pressure.pressure
+        .and_then(|q| exact(&q.value).map(|p| (p, q.units)))
+        .is_some_and(|(p, units)| match units {
+            Unit::InchOfMercury => !(25.0..=32.5).contains(&p),
+            _ => !(850.0..=1090.0).contains(&p),
+        })
+}
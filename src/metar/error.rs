@@ -0,0 +1,145 @@
+//! Module for errors of the METAR decoding.
+//!
+//! A group is invalid when it is recognized by its format but its content is invalid, e.g. an observation time `122575Z`.
+//! Invalid groups are recorded in [Diagnostics::invalid_groups](super::diagnostics::Diagnostics::invalid_groups) and the decoding continues,
+//! unless they are rejected with [DecodeOptions::reject_invalid()](super::DecodeOptions::reject_invalid).
+//! Groups in the remark section are free-form and never fail the decoding, malformed ones are kept in [Remarks::unparsed](super::remark::Remarks::unparsed).
+
+use std::{fmt, ops::Range};
+
+use regex::Captures;
+use serde::{Serialize, Deserialize};
+
+/// Invalid group of a report.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvalidGroup {
+    /// Byte range of the group in the sanitized report, see [Metar::report](super::Metar::report).
+    pub span: Range<usize>,
+    /// Offending group.
+    pub token: String,
+}
+
+impl InvalidGroup {
+    /// Creates an invalid group from the whole match of a handler, relative to the text given to the handler.
+    pub(crate) fn from_capture(capture: &Captures) -> InvalidGroup {
+        let matched = capture.get(0).unwrap();
+        let token = matched.as_str().trim_end();
+
+        InvalidGroup { span: matched.start()..matched.start() + token.len(), token: token.to_string() }
+    }
+}
+
+/// Error of the METAR decoding.
+///
+/// Variants correspond to the kinds of groups in the main and TREND sections.
+///
+/// JSON representation is internally tagged and in lowercase snake case. Example:
+/// ```json
+/// {
+///     "group_type": "header",
+///     "span": {
+///         "start": 0,
+///         "end": 12
+///     },
+///     "token": "LKPR 122575Z"
+/// }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "group_type", rename_all = "snake_case")]
+pub enum DecodeError {
+    /// Identification groups, e.g. an invalid observation time.
+    Header(InvalidGroup),
+    /// Surface wind group.
+    Wind(InvalidGroup),
+    /// Visibility group.
+    Visibility(InvalidGroup),
+    /// Runway visual range group.
+    RunwayVisualRange(InvalidGroup),
+    /// Cloud group.
+    Cloud(InvalidGroup),
+    /// Temperature group.
+    Temperature(InvalidGroup),
+    /// Pressure group.
+    Pressure(InvalidGroup),
+    /// Wind shear group.
+    WindShear(InvalidGroup),
+    /// Sea surface temperature and state of the sea group.
+    Sea(InvalidGroup),
+    /// Time group of a TREND forecast.
+    TrendTime(InvalidGroup),
+}
+
+impl DecodeError {
+    /// Returns the name of the group kind.
+    pub fn group(&self) -> &'static str {
+        match self {
+            DecodeError::Header(_) => "header",
+            DecodeError::Wind(_) => "wind",
+            DecodeError::Visibility(_) => "visibility",
+            DecodeError::RunwayVisualRange(_) => "runway visual range",
+            DecodeError::Cloud(_) => "cloud",
+            DecodeError::Temperature(_) => "temperature",
+            DecodeError::Pressure(_) => "pressure",
+            DecodeError::WindShear(_) => "wind shear",
+            DecodeError::Sea(_) => "sea",
+            DecodeError::TrendTime(_) => "trend time",
+        }
+    }
+
+    /// Returns the invalid group.
+    pub fn invalid_group(&self) -> &InvalidGroup {
+        match self {
+            DecodeError::Header(ig)
+            | DecodeError::Wind(ig)
+            | DecodeError::Visibility(ig)
+            | DecodeError::RunwayVisualRange(ig)
+            | DecodeError::Cloud(ig)
+            | DecodeError::Temperature(ig)
+            | DecodeError::Pressure(ig)
+            | DecodeError::WindShear(ig)
+            | DecodeError::Sea(ig)
+            | DecodeError::TrendTime(ig) => ig,
+        }
+    }
+
+    /// Returns the byte range of the invalid group in the sanitized report.
+    pub fn span(&self) -> Range<usize> {
+        self.invalid_group().span.clone()
+    }
+
+    /// Returns the invalid group.
+    pub fn token(&self) -> &str {
+        &self.invalid_group().token
+    }
+
+    /// Shifts the byte range by `offset`, used to convert ranges relative to a handler text into report ranges.
+    pub(crate) fn offset(mut self, offset: usize) -> DecodeError {
+        let ig = match &mut self {
+            DecodeError::Header(ig)
+            | DecodeError::Wind(ig)
+            | DecodeError::Visibility(ig)
+            | DecodeError::RunwayVisualRange(ig)
+            | DecodeError::Cloud(ig)
+            | DecodeError::Temperature(ig)
+            | DecodeError::Pressure(ig)
+            | DecodeError::WindShear(ig)
+            | DecodeError::Sea(ig)
+            | DecodeError::TrendTime(ig) => ig,
+        };
+
+        ig.span = ig.span.start + offset..ig.span.end + offset;
+
+        self
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.span();
+        write!(f, "Invalid {} group, given {} at {}..{}", self.group(), self.token(), span.start, span.end)
+    }
+}
+
+impl std::error::Error for DecodeError {}
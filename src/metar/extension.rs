@@ -0,0 +1,93 @@
+//! Module for extending the decoding of the main and TREND sections of METAR reports.
+//!
+//! Library users can add handlers of groups which are not decoded by this crate to a [Decoder](super::Decoder),
+//! e.g. vendor-specific groups of some automated stations. Decoded groups are stored in [Metar::extensions](super::Metar::extensions).
+
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+
+/// Section of the report where a group handler is consulted.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupSection {
+    Main,
+    Trend,
+}
+
+/// Placement of a group handler relative to the built-in handlers.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandlerPlacement {
+    /// Consulted before the built-in handlers, so the handler can override them.
+    BeforeBuiltIn,
+    /// Consulted after the built-in handlers, i.e. only for groups which would end up unparsed.
+    AfterBuiltIn,
+}
+
+/// Handler of custom groups in the main or TREND section.
+pub trait GroupHandler: Send + Sync {
+    /// Decoded item.
+    type Item: Serialize;
+
+    /// Name of the decoded group, used as the key in [Metar::extensions](super::Metar::extensions).
+    fn name(&self) -> &str;
+
+    /// Decodes a group at the start of `text`.
+    ///
+    /// `text` is the remaining text of the report and always ends with a whitespace.
+    /// Returns the decoded item and the length of the consumed text including the whitespace that ends the group,
+    /// [None] if there is no such group at the start of `text`.
+    fn handle(&self, text: &str) -> Option<(Self::Item, usize)>;
+}
+
+/// Object-safe counterpart of [GroupHandler] that allows to store handlers with different items together.
+pub(super) trait DynGroupHandler: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn handle_value(&self, text: &str) -> Option<(serde_json::Value, usize)>;
+}
+
+impl<H: GroupHandler> DynGroupHandler for H {
+    fn name(&self) -> &str {
+        GroupHandler::name(self)
+    }
+
+    fn handle_value(&self, text: &str) -> Option<(serde_json::Value, usize)> {
+        self.handle(text)
+            .and_then(|(item, end)| serde_json::to_value(item).ok().map(|value| (value, end)))
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct RegisteredHandler {
+    section: GroupSection,
+    placement: HandlerPlacement,
+    priority: i32,
+    handler: Arc<dyn DynGroupHandler>,
+}
+
+/// Adds a handler to `handlers` ordered by the priority, handlers with the same priority in the order they are added.
+pub(super) fn insert_handler<H: GroupHandler + 'static>(handlers: &mut Vec<RegisteredHandler>, section: GroupSection, placement: HandlerPlacement, priority: i32, handler: H) {
+    let position = handlers.iter().position(|h| h.priority < priority).unwrap_or(handlers.len());
+    handlers.insert(position, RegisteredHandler { section, placement, priority, handler: Arc::new(handler) });
+}
+
+/// Consults the handlers of the given section and placement.
+/// Returns the handler name, the decoded item and the end index.
+pub(super) fn handle_group(handlers: &[RegisteredHandler], section: GroupSection, placement: HandlerPlacement, text: &str) -> Option<(String, serde_json::Value, usize)> {
+    handlers.iter()
+        .filter(|h| h.section == section && h.placement == placement)
+        .find_map(|h| {
+            h.handler.handle_value(text)
+                // the handler must consume a whole group
+                .filter(|(_, end)| text.get(..*end).is_some_and(|t| t.ends_with(char::is_whitespace)))
+                .map(|(value, end)| (h.handler.name().to_string(), value, end))
+        })
+}
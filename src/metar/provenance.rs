@@ -0,0 +1,52 @@
+//! Module for the provenance of decoded elements.
+//!
+//! Provenance records which part of [Metar::report](super::Metar::report) produced each decoded element,
+//! e.g. for highlighting the groups of a report in a user interface. It is enabled by [DecodeOptions::provenance()](super::DecodeOptions::provenance).
+
+use std::{collections::BTreeMap, fmt, ops::Range};
+
+use serde::{Serialize, Deserialize};
+
+/// Source spans of decoded elements.
+///
+/// Elements are keyed by their path in [Metar](super::Metar), e.g. `wind`, `clouds[1]`, `trend_changes[0].visibility`,
+/// `remarks.peak_wind` or `extensions.awos[0]`. Spans are byte ranges in the sanitized report, see [Metar::report](super::Metar::report).
+///
+/// JSON representation is an object keyed by the paths. Example:
+/// ```json
+/// {
+///     "clouds[0]": {"start": 26, "end": 32},
+///     "wind": {"start": 13, "end": 20}
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Provenance(BTreeMap<String, Range<usize>>);
+
+impl Provenance {
+    /// Returns the span of the element with the given path.
+    pub fn get(&self, path: &str) -> Option<&Range<usize>> {
+        self.0.get(path)
+    }
+
+    /// Returns an iterator over the paths and spans, ordered by the paths.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Range<usize>)> {
+        self.0.iter().map(|(path, span)| (path.as_str(), span))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Records the span of a decoded element without the trailing whitespace.
+    pub(super) fn record(&mut self, path: fmt::Arguments, span: Range<usize>, report: &str) {
+        let end = span.end.min(report.len());
+        let token = report[span.start..end].trim_end();
+
+        self.0.insert(path.to_string(), span.start..span.start + token.len());
+    }
+}
@@ -0,0 +1,1713 @@
+//! Module for decoding the remark section (RMK) of METAR reports.
+//!
+//! Remarks are mostly given by national coding practices. The decoding is written based on the following publications:
+//! - World Meteorological Organization (2018). Manual on Codes, Volume II – Regional Codes and National Coding Practices. Available: <https://library.wmo.int/idurl/4/35717>.
+//! - Office of the Federal Coordinator for Meteorology (2019). Federal Meteorological Handbook No. 1: Surface Weather Observations and Reports (FCM-H1-2019).
+
+use std::{str::FromStr, sync::Arc};
+
+use anyhow::{anyhow, Error, Result};
+use chrono::{Datelike, NaiveDate, NaiveTime, Timelike};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+
+use crate::{datetime::{UtcDateTime, UtcDayTime, UtcTime}, number::Number};
+
+use super::{AnchorPolicy, CloudCover, CloudLayer, CloudType, Decoder, DirectionOctant, Metar, MetarTime, NilReason, Pressure, Quantity, Runway, Temperature, Unit, Value, WeatherDescriptor, WeatherPhenomena};
+
+lazy_static! {
+    static ref STATION_OPERATION_RE: Regex = Regex::new(r"(?x)
+        ^(?P<operation>NOSPECI|FIRST|LAST)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref AUTOMATED_STATION_TYPE_RE: Regex = Regex::new(r"(?x)
+        ^A[O0](?P<station_type>[12])A?
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref SEA_LEVEL_PRESSURE_RE: Regex = Regex::new(r"(?x)
+        ^SLP(?P<pressure>\d\d\d|NO)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref PRECISE_TEMPERATURE_RE: Regex = Regex::new(r"(?x)
+        ^T(?P<temperature>[01]\d\d\d)
+        (?P<dew_point>[01]\d\d\d|////)?
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref HOURLY_PRECIPITATION_RE: Regex = Regex::new(r"(?x)
+        ^P(?P<amount>\d{4}|////)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref THREE_OR_SIX_HOUR_PRECIPITATION_RE: Regex = Regex::new(r"(?x)
+        ^6(?P<amount>\d{4}|////)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref TWENTY_FOUR_HOUR_PRECIPITATION_RE: Regex = Regex::new(r"(?x)
+        ^7(?P<amount>\d{4}|////)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref SNOW_DEPTH_RE: Regex = Regex::new(r"(?x)
+        ^4/(?P<depth>\d{3})
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref METRIC_SNOW_DEPTH_RE: Regex = Regex::new(r"(?x)
+        ^(SNOW\sDEPTH|SNW\sDPT)\s
+        (?P<depth>\d{1,3})
+        (?P<units>CM|MM)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref SNOW_WATER_EQUIVALENT_RE: Regex = Regex::new(r"(?x)
+        ^933(?P<amount>\d{3})
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref SIX_HOUR_SNOWFALL_RE: Regex = Regex::new(r"(?x)
+        ^931(?P<amount>\d{3}|///)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref SIX_HOUR_MAXIMUM_TEMPERATURE_RE: Regex = Regex::new(r"(?x)
+        ^1(?P<temperature>[01]\d\d\d)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref SIX_HOUR_MINIMUM_TEMPERATURE_RE: Regex = Regex::new(r"(?x)
+        ^2(?P<temperature>[01]\d\d\d)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref TWENTY_FOUR_HOUR_TEMPERATURE_RE: Regex = Regex::new(r"(?x)
+        ^4(?P<maximum>[01]\d\d\d)
+        (?P<minimum>[01]\d\d\d)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref PRESSURE_TENDENCY_RE: Regex = Regex::new(r"(?x)
+        ^5(?P<characteristic>[0-8])
+        (?P<change>\d\d\d)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref RAPID_PRESSURE_CHANGE_RE: Regex = Regex::new(r"(?x)
+        ^(?P<change>PRESRR|PRESFR)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref PEAK_WIND_RE: Regex = Regex::new(r"(?x)
+        ^PK\sWND\s
+        (?P<direction>\d\d\d)
+        (?P<speed>\d\d\d?)
+        /(?P<hour>\d\d)?(?P<minute>\d\d)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref WIND_SHIFT_RE: Regex = Regex::new(r"(?x)
+        ^WSHFT\s
+        (?P<hour>\d\d)?(?P<minute>\d\d)
+        (\s(?P<fropa>FROPA))?
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref WEATHER_EVENTS_RE: Regex = Regex::new(r"(?x)
+        ^(?P<events>
+            ((MI|BC|PR|DR|BL|SH|TS|FZ|DZ|RA|SN|SG|PL|GR|GS|UP|BR|FG|FU|VA|DU|SA|HZ|PO|SQ|FC|SS|DS|IC|PY)+
+            ([BE](\d\d){1,2})+)+
+        )
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref WEATHER_EVENT_RE: Regex = Regex::new(r"(?x)
+        (?P<code>(MI|BC|PR|DR|BL|SH|TS|FZ|DZ|RA|SN|SG|PL|GR|GS|UP|BR|FG|FU|VA|DU|SA|HZ|PO|SQ|FC|SS|DS|IC|PY)+)
+        (?P<times>([BE](\d\d){1,2})+)
+    ").unwrap();
+
+    static ref WEATHER_EVENT_TIME_RE: Regex = Regex::new(r"(?x)
+        (?P<indicator>[BE])
+        (?P<hour>\d\d)?
+        (?P<minute>\d\d)
+    ").unwrap();
+
+    static ref CONVECTIVE_ACTIVITY_RE: Regex = Regex::new(r"(?x)
+        ^(?P<phenomenon>
+            ((?P<frequency>OCNL|FRQ|CONS)\s)?LTG(?P<types>(IC|CC|CG|CA)*)
+            |TS|CBMAM|CB|TCU|ACC|VIRGA
+        )
+        (\s(?P<distance>OHD|VC|DSNT))?
+        (\s(?P<directions>ALQD?S|[NSEW]{1,2}((-|\sAND\s)[NSEW]{1,2})*))?
+        (\sMOVD?\s(?P<movement>[NSEW]{1,2}))?
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref TOWER_VISIBILITY_RE: Regex = Regex::new(r"(?x)
+        ^TWR\sVIS\s
+        (?P<visibility>M?(\d+\s)?\d/\d{1,2}|M?\d{1,4})
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref SURFACE_VISIBILITY_RE: Regex = Regex::new(r"(?x)
+        ^SFC\sVIS\s
+        (?P<visibility>M?(\d+\s)?\d/\d{1,2}|M?\d{1,4})
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref VARIABLE_VISIBILITY_RE: Regex = Regex::new(r"(?x)
+        ^VIS\s
+        (?P<visibility>(M?(\d+\s)?\d/\d{1,2}|M?\d{1,4})V(P?(\d+\s)?\d/\d{1,2}|P?\d{1,4}))
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref SECOND_SITE_VISIBILITY_RE: Regex = Regex::new(r"(?x)
+        ^VIS\s
+        (?P<visibility>M?(\d+\s)?\d/\d{1,2}|M?\d{1,4})
+        \sRWY(?P<runway>\d\d[LCR]?)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref VARIABLE_CEILING_RE: Regex = Regex::new(r"(?x)
+        ^CIG\s
+        (?P<ceiling>\d\d\dV\d\d\d)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref SECOND_SITE_CEILING_RE: Regex = Regex::new(r"(?x)
+        ^CIG\s
+        (?P<ceiling>\d\d\d)
+        \sRWY(?P<runway>\d\d[LCR]?)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref MISSING_SENSOR_RE: Regex = Regex::new(r"(?x)
+        ^(?P<sensor>RVRNO|PWINO|PNO|FZRANO|TSNO|VISNO|CHINO)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref MISSING_SECONDARY_SENSOR_RE: Regex = Regex::new(r"(?x)
+        ^(?P<sensor>VISNO|CHINO)
+        \sRWY(?P<runway>\d\d[LCR]?)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref MAINTENANCE_INDICATOR_RE: Regex = Regex::new(r"(?x)
+        ^\$
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref CLOUD_OPACITIES_RE: Regex = Regex::new(r"(?x)
+        ^(?P<layers>
+            ((CI|CS|CC|ACC|AC|AS|NS|SC|SF|ST|CF|CU|TCU|CB|FG|BR|HZ|FU|SN|DZ|RA|DU|SA|VA|IC|PL|SG|GS|GR)[1-8])+
+        )
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref CLOUD_OPACITY_RE: Regex = Regex::new(r"(?x)
+        (?P<layer_type>[A-Z]+)
+        (?P<oktas>[1-8])
+    ").unwrap();
+
+    static ref DENSITY_ALTITUDE_RE: Regex = Regex::new(r"(?x)
+        ^DENSITY\sALT\s
+        (?P<altitude>-?\d{1,5})FT
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref HAIL_SIZE_RE: Regex = Regex::new(r"(?x)
+        ^GR\s
+        (?P<below>LESS\sTHAN\s)?
+        (?P<size>(\d\s)?\d/\d|\d)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref METRIC_HAIL_SIZE_RE: Regex = Regex::new(r"(?x)
+        ^GR\s
+        (?P<size>\d{1,3})
+        (?P<units>CM|MM)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref CLOUD_BASE_RE: Regex = Regex::new(r"(?x)
+        ^QBB(?P<height>\d\d\d\d?)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref STATION_PRESSURE_RE: Regex = Regex::new(r"(?x)
+        ^QFE(?P<mmhg>\d\d\d)
+        (/(?P<hpa>\d\d\d\d?))?
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref MOUNTAINS_OBSCURED_RE: Regex = Regex::new(r"(?x)
+        ^MT\sOBSC
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref OKTA_CLOUD_LAYER_RE: Regex = Regex::new(r"(?x)
+        ^(?P<oktas>[1-8])
+        (?P<cloud>CI|CS|CC|AC|AS|NS|SC|ST|CU|CB|TCU)
+        (?P<height>\d\d\d)
+        (?P<end>\s)
+    ").unwrap();
+
+    static ref SNOW_INCREASING_RAPIDLY_RE: Regex = Regex::new(r"(?x)
+        ^SNINCR
+        \s
+        (?P<increase>\d{1,2})
+        /
+        (?P<depth>\d{1,3})
+        (?P<end>\s)
+    ").unwrap();
+}
+
+/// Decoded remark group.
+///
+/// JSON representation is internally tagged and in lowercase snake case. Example:
+/// ```json
+/// {
+///     "remark_type": "last_report"
+/// }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "remark_type", rename_all = "snake_case")]
+pub enum Remark {
+    /// No SPECI reports are issued by the manned station (`NOSPECI`).
+    NoSpeci,
+    /// First report after the manned station was closed (`FIRST`).
+    FirstReport,
+    /// Last report before the manned station closes (`LAST`).
+    LastReport,
+    /// Remark group decoded by a custom [RemarkDecoder].
+    Custom {
+        /// Name of the decoder, see [RemarkDecoder::name()].
+        name: String,
+        /// Decoded item in its JSON representation.
+        value: serde_json::Value,
+    },
+}
+
+impl FromStr for Remark {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NOSPECI" => Ok(Remark::NoSpeci),
+            "FIRST" => Ok(Remark::FirstReport),
+            "LAST" => Ok(Remark::LastReport),
+            _ => Err(anyhow!("Invalid remark, given {}", s))
+        }
+    }
+}
+
+/// Decoder of custom remark groups, e.g. groups of national coding practices not decoded by this crate.
+///
+/// Decoders added to a [Decoder] are consulted in the remark section after all the built-in decoders,
+/// their decoded items are stored as [Remark::Custom] in [Remarks::items]. See [Decoder::remark_decoder()](super::Decoder::remark_decoder).
+pub trait RemarkDecoder: Send + Sync {
+    /// Decoded item.
+    type Item: Serialize;
+
+    /// Name of the decoded remark group, e.g. `runway_state`.
+    fn name(&self) -> &str;
+
+    /// Decodes a remark group at the start of `text`.
+    ///
+    /// `text` is the remaining text of the report and always ends with a whitespace.
+    /// Returns the decoded item and the length of the consumed text including the whitespace that ends the group,
+    /// [None] if there is no such group at the start of `text`.
+    fn decode(&self, text: &str) -> Option<(Self::Item, usize)>;
+}
+
+/// Object-safe counterpart of [RemarkDecoder] that allows to store decoders with different items together.
+pub(super) trait DynRemarkDecoder: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn decode_value(&self, text: &str) -> Option<(serde_json::Value, usize)>;
+}
+
+impl<D: RemarkDecoder> DynRemarkDecoder for D {
+    fn name(&self) -> &str {
+        RemarkDecoder::name(self)
+    }
+
+    fn decode_value(&self, text: &str) -> Option<(serde_json::Value, usize)> {
+        self.decode(text)
+            .and_then(|(item, end)| serde_json::to_value(item).ok().map(|value| (value, end)))
+    }
+}
+
+/// Type of an automated station.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomatedStationType {
+    /// Station without a precipitation discriminator (`AO1`).
+    WithoutPrecipitationDiscriminator,
+    /// Station with a precipitation discriminator (`AO2`), i.e. able to distinguish between liquid and frozen precipitation.
+    WithPrecipitationDiscriminator,
+}
+
+impl FromStr for AutomatedStationType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(AutomatedStationType::WithoutPrecipitationDiscriminator),
+            "2" => Ok(AutomatedStationType::WithPrecipitationDiscriminator),
+            _ => Err(anyhow!("Invalid automated station type, given {}", s))
+        }
+    }
+}
+
+/// Precipitation amount.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct PrecipitationAmount {
+    /// Amount of the precipitation. In case of a trace, it is below the resolution of the amount.
+    pub amount: Option<Quantity>,
+    /// Flag if only a trace of precipitation, too small to be measured, was reported.
+    pub is_trace: bool,
+    /// Reason of the missing amount, e.g. an indeterminable amount reported as `////`.
+    pub amount_nil_reason: Option<NilReason>,
+}
+
+/// Rapid increase of the snow depth (`SNINCR`).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnowIncrease {
+    /// Increase of the snow depth during the last hour.
+    pub hourly_increase: Quantity,
+    /// Total snow depth on the ground.
+    pub snow_depth: Quantity,
+}
+
+/// Temperature extremes over a period.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct TemperatureExtremes {
+    pub maximum_temperature: Option<Quantity>,
+    pub minimum_temperature: Option<Quantity>,
+}
+
+/// Characteristic of the pressure tendency during the last 3 hours from WMO Code Table 0200.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PressureCharacteristic {
+    /// Increasing, then decreasing; pressure the same or higher than 3 hours ago.
+    IncreasingThenDecreasing,
+    /// Increasing, then steady; or increasing, then increasing more slowly.
+    IncreasingThenSteady,
+    /// Increasing (steadily or unsteadily).
+    Increasing,
+    /// Decreasing or steady, then increasing; or increasing, then increasing more rapidly.
+    SteadyOrDecreasingThenIncreasing,
+    /// Steady; pressure the same as 3 hours ago.
+    Steady,
+    /// Decreasing, then increasing; pressure the same or lower than 3 hours ago.
+    DecreasingThenIncreasing,
+    /// Decreasing, then steady; or decreasing, then decreasing more slowly.
+    DecreasingThenSteady,
+    /// Decreasing (steadily or unsteadily).
+    Decreasing,
+    /// Steady or increasing, then decreasing; or decreasing, then decreasing more rapidly.
+    SteadyOrIncreasingThenDecreasing,
+}
+
+impl FromStr for PressureCharacteristic {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" => Ok(PressureCharacteristic::IncreasingThenDecreasing),
+            "1" => Ok(PressureCharacteristic::IncreasingThenSteady),
+            "2" => Ok(PressureCharacteristic::Increasing),
+            "3" => Ok(PressureCharacteristic::SteadyOrDecreasingThenIncreasing),
+            "4" => Ok(PressureCharacteristic::Steady),
+            "5" => Ok(PressureCharacteristic::DecreasingThenIncreasing),
+            "6" => Ok(PressureCharacteristic::DecreasingThenSteady),
+            "7" => Ok(PressureCharacteristic::Decreasing),
+            "8" => Ok(PressureCharacteristic::SteadyOrIncreasingThenDecreasing),
+            _ => Err(anyhow!("Invalid pressure characteristic, given {}", s))
+        }
+    }
+}
+
+/// Pressure tendency during the last 3 hours (`5appp`).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PressureTendency {
+    pub characteristic: PressureCharacteristic,
+    /// Change of the pressure, negative if the pressure is lower than 3 hours ago.
+    pub pressure_change: Quantity,
+}
+
+/// Rapid pressure change.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RapidPressureChange {
+    /// Pressure rising rapidly (`PRESRR`).
+    Rising,
+    /// Pressure falling rapidly (`PRESFR`).
+    Falling,
+}
+
+impl FromStr for RapidPressureChange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "PRESRR" => Ok(RapidPressureChange::Rising),
+            "PRESFR" => Ok(RapidPressureChange::Falling),
+            _ => Err(anyhow!("Invalid rapid pressure change, given {}", s))
+        }
+    }
+}
+
+/// Peak wind since the last routine report (`PK WND`).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PeakWind {
+    pub wind_from_direction: Quantity,
+    pub wind_speed: Quantity,
+    /// Time of the peak wind, [None] if it cannot be resolved.
+    pub time: Option<MetarTime>,
+}
+
+/// Wind shift (`WSHFT`).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindShift {
+    /// Time when the wind shift began, [None] if it cannot be resolved.
+    pub time: Option<MetarTime>,
+    /// Wind shift associated with a frontal passage (`FROPA`).
+    pub is_frontal_passage: bool,
+}
+
+/// Beginning and ending of a weather phenomenon (e.g. `RAB15E30`).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeatherEvent {
+    pub descriptors: Vec<WeatherDescriptor>,
+    pub phenomena: Vec<WeatherPhenomena>,
+    /// Time when the weather began, [None] if not reported or if it cannot be resolved.
+    pub begin_time: Option<MetarTime>,
+    /// Time when the weather ended, [None] if not reported or if it cannot be resolved.
+    pub end_time: Option<MetarTime>,
+}
+
+/// Octants in the clockwise order starting from the north.
+const OCTANTS: [DirectionOctant; 8] = [
+    DirectionOctant::North,
+    DirectionOctant::NorthEast,
+    DirectionOctant::East,
+    DirectionOctant::SouthEast,
+    DirectionOctant::South,
+    DirectionOctant::SouthWest,
+    DirectionOctant::West,
+    DirectionOctant::NorthWest,
+];
+
+/// Convective phenomenon.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConvectivePhenomenon {
+    Lightning,
+    Thunderstorm,
+    Cumulonimbus,
+    CumulonimbusMammatus,
+    ToweringCumulus,
+    AltocumulusCastellanus,
+    Virga,
+}
+
+impl FromStr for ConvectivePhenomenon {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "LTG" => Ok(ConvectivePhenomenon::Lightning),
+            "TS" => Ok(ConvectivePhenomenon::Thunderstorm),
+            "CB" => Ok(ConvectivePhenomenon::Cumulonimbus),
+            "CBMAM" => Ok(ConvectivePhenomenon::CumulonimbusMammatus),
+            "TCU" => Ok(ConvectivePhenomenon::ToweringCumulus),
+            "ACC" => Ok(ConvectivePhenomenon::AltocumulusCastellanus),
+            "VIRGA" => Ok(ConvectivePhenomenon::Virga),
+            _ => Err(anyhow!("Invalid convective phenomenon, given {}", s))
+        }
+    }
+}
+
+/// Lightning frequency.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LightningFrequency {
+    /// Less than 1 flash per minute (`OCNL`).
+    Occasional,
+    /// About 1 to 6 flashes per minute (`FRQ`).
+    Frequent,
+    /// More than 6 flashes per minute (`CONS`).
+    Continuous,
+}
+
+impl FromStr for LightningFrequency {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "OCNL" => Ok(LightningFrequency::Occasional),
+            "FRQ" => Ok(LightningFrequency::Frequent),
+            "CONS" => Ok(LightningFrequency::Continuous),
+            _ => Err(anyhow!("Invalid lightning frequency, given {}", s))
+        }
+    }
+}
+
+/// Lightning type.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LightningType {
+    InCloud,
+    CloudToCloud,
+    CloudToGround,
+    CloudToAir,
+}
+
+impl FromStr for LightningType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "IC" => Ok(LightningType::InCloud),
+            "CC" => Ok(LightningType::CloudToCloud),
+            "CG" => Ok(LightningType::CloudToGround),
+            "CA" => Ok(LightningType::CloudToAir),
+            _ => Err(anyhow!("Invalid lightning type, given {}", s))
+        }
+    }
+}
+
+/// Distance of a phenomenon from the station.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistanceQualifier {
+    /// Over the station (`OHD`).
+    Overhead,
+    /// From 5 to 10 statute miles from the station (`VC`).
+    Vicinity,
+    /// Beyond 10 statute miles from the station (`DSNT`).
+    Distant,
+}
+
+impl FromStr for DistanceQualifier {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "OHD" => Ok(DistanceQualifier::Overhead),
+            "VC" => Ok(DistanceQualifier::Vicinity),
+            "DSNT" => Ok(DistanceQualifier::Distant),
+            _ => Err(anyhow!("Invalid distance qualifier, given {}", s))
+        }
+    }
+}
+
+/// Lightning, thunderstorm location or significant convective cloud (e.g. `FRQ LTGICCG DSNT NE-SE`, `TS OHD MOV E`).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConvectiveActivity {
+    pub phenomenon: ConvectivePhenomenon,
+    /// Frequency of the lightning.
+    pub frequency: Option<LightningFrequency>,
+    pub lightning_types: Vec<LightningType>,
+    pub distance: Option<DistanceQualifier>,
+    /// Octants where the phenomenon is observed, ranges (e.g. `NE-SE`) are expanded clockwise
+    /// and all quadrants (`ALQDS`) are expanded to all octants.
+    pub directions: Vec<DirectionOctant>,
+    /// Direction of the movement.
+    pub movement: Option<DirectionOctant>,
+}
+
+/// Value observed at a second location (e.g. `VIS 2 RWY11`, `CIG 002 RWY11`).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecondSiteQuantity {
+    pub quantity: Quantity,
+    pub runway: Runway,
+}
+
+/// Sensor of an automated station.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sensor {
+    /// Runway visual range (`RVRNO`).
+    RunwayVisualRange,
+    /// Present weather identifier (`PWINO`).
+    PresentWeatherIdentifier,
+    /// Precipitation amount (`PNO`).
+    Precipitation,
+    /// Freezing rain (`FZRANO`).
+    FreezingRain,
+    /// Lightning detection (`TSNO`).
+    Lightning,
+    /// Secondary visibility (`VISNO`).
+    Visibility,
+    /// Secondary ceiling height indicator (`CHINO`).
+    Ceiling,
+}
+
+impl FromStr for Sensor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "RVRNO" => Ok(Sensor::RunwayVisualRange),
+            "PWINO" => Ok(Sensor::PresentWeatherIdentifier),
+            "PNO" => Ok(Sensor::Precipitation),
+            "FZRANO" => Ok(Sensor::FreezingRain),
+            "TSNO" => Ok(Sensor::Lightning),
+            "VISNO" => Ok(Sensor::Visibility),
+            "CHINO" => Ok(Sensor::Ceiling),
+            _ => Err(anyhow!("Invalid sensor, given {}", s))
+        }
+    }
+}
+
+/// Sensor which is not available.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissingSensor {
+    pub sensor: Sensor,
+    /// Location of a secondary sensor (e.g. `VISNO RWY06`).
+    pub runway: Option<Runway>,
+}
+
+/// Health of the sensors of an automated station given in the remark section.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SensorStatus {
+    pub missing_sensors: Vec<MissingSensor>,
+    /// Flag if the station requires maintenance (`$`).
+    pub is_maintenance_needed: bool,
+}
+
+impl SensorStatus {
+    /// Returns `true` if any sensor is missing or the station requires maintenance.
+    pub fn is_degraded(&self) -> bool {
+        !self.missing_sensors.is_empty() || self.is_maintenance_needed
+    }
+}
+
+/// Type and opacity of a cloud layer or a layer of obscuring phenomena in Canadian reports (e.g. `SC6` in `SC6AC2`).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CloudOpacity {
+    /// Cloud type, [None] for layers of obscuring phenomena.
+    pub cloud_type: Option<CloudType>,
+    /// Obscuring phenomenon (e.g. `FG`, `SN`), [None] for cloud layers.
+    pub obscuration: Option<WeatherPhenomena>,
+    /// Opacity of the layer in oktas.
+    pub oktas: u8,
+    /// Index of the corresponding layer in [Metar::clouds](super::Metar::clouds), [None] if the layers
+    /// in the remark section do not match those in the main section.
+    pub cloud_layer_index: Option<usize>,
+}
+
+/// Region with its own national coding practices of the remark section.
+///
+/// National remark groups are decoded only in reports from the corresponding region,
+/// so that they do not misfire on reports from other countries. This includes the coded groups of FMH-1
+/// (e.g. `AO2`, `SLP982`, `T01940139`, `6RRRR`, `4/sss`, `5appp` or `PK WND`) and the hail size in inches
+/// (`GR 1 3/4`). Reports following FMH-1 outside the United States, e.g. from US military bases abroad,
+/// are decoded by setting the region explicitly, see [DecodeOptions::region](super::DecodeOptions::region).
+///
+/// Groups whose format does not depend on the region are decoded for all stations. These are the plain
+/// language groups used internationally, i.e. convective activity (e.g. `CB DSNT N`), visibility
+/// (e.g. `VIS 1600V2400`), ceiling (e.g. `CIG 005V010`), density altitude and station operation (e.g. `LAST`),
+/// and the groups of national practices of several states, i.e. station pressure (`QFE`), obscured mountains
+/// (`MT OBSC`), and hail size (e.g. `GR 15MM`) and snow depth (e.g. `SNW DPT 35CM`) in metric units.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemarkRegion {
+    /// United States, ICAO location indicators starting with `K` or `P`. Groups of FMH-1.
+    UnitedStates,
+    /// Canada, ICAO location indicators starting with `C`. Groups of FMH-1 reported by Canadian stations as well,
+    /// i.e. automated station type (`AO1`, `AO2`), sea level pressure (`SLP`), precise temperature (`T`),
+    /// pressure tendency (`5appp`), rapid pressure change (`PRESRR`, `PRESFR`), peak wind (`PK WND`)
+    /// and cloud opacities (e.g. `SC6AC2`).
+    Canada,
+    /// Russian Federation and other states with ICAO location indicators starting with `U`. Cloud base (`QBB`).
+    Russia,
+    /// Japan, ICAO location indicators starting with `RJ` or `RO`. Cloud layers in oktas (e.g. `1CU020`).
+    Japan,
+}
+
+impl FromStr for RemarkRegion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "united-states" => Ok(RemarkRegion::UnitedStates),
+            "canada" => Ok(RemarkRegion::Canada),
+            "russia" => Ok(RemarkRegion::Russia),
+            "japan" => Ok(RemarkRegion::Japan),
+            _ => Err(anyhow!("Invalid remark region, given {}", s))
+        }
+    }
+}
+
+impl RemarkRegion {
+    /// Returns the region of a station given by its ICAO location indicator, [None] if unknown.
+    pub fn from_station_id(station_id: &str) -> Option<RemarkRegion> {
+        if station_id.starts_with('K') || station_id.starts_with('P') {
+            Some(RemarkRegion::UnitedStates)
+        } else if station_id.starts_with('C') {
+            Some(RemarkRegion::Canada)
+        } else if station_id.starts_with('U') {
+            Some(RemarkRegion::Russia)
+        } else if station_id.starts_with("RJ") || station_id.starts_with("RO") {
+            Some(RemarkRegion::Japan)
+        } else {
+            None
+        }
+    }
+}
+
+/// Cloud layer with its amount in oktas reported in Japan (e.g. `1CU020`).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OktaCloudLayer {
+    pub oktas: u8,
+    pub cloud_type: CloudType,
+    /// Height above the ground level (AGL).
+    pub height: Quantity,
+}
+
+/// Remark section.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Remarks {
+    /// Text of the remark section as reported, without the RMK indicator.
+    pub raw: Option<String>,
+    /// Type of the automated station (`AO1`, `AO2`).
+    pub automated_station_type: Option<AutomatedStationType>,
+    /// Sea-level pressure in tenths of a hectopascal (`SLPppp`).
+    pub sea_level_pressure: Option<Pressure>,
+    /// Temperature and dew point in tenths of a degree Celsius (`TsnTTTsnTdTdTd`).
+    pub precise_temperature: Option<Temperature>,
+    /// Precipitation amount during the last hour (`Prrrr`).
+    pub hourly_precipitation: Option<PrecipitationAmount>,
+    /// Precipitation amount during the last 6 hours in reports around 00, 06, 12 and 18 UTC,
+    /// during the last 3 hours otherwise (`6RRRR`).
+    pub three_or_six_hour_precipitation: Option<PrecipitationAmount>,
+    /// Precipitation amount during the last 24 hours (`7RRRR`).
+    pub twenty_four_hour_precipitation: Option<PrecipitationAmount>,
+    /// Total snow depth on the ground (`4/sss` in the United States, or in metric units, e.g. `SNW DPT 35CM`).
+    pub snow_depth: Option<Quantity>,
+    /// Water equivalent of the snow on the ground (`933RRR`).
+    pub snow_water_equivalent: Option<Quantity>,
+    /// Snowfall during the last 6 hours (`931sss`).
+    pub six_hour_snowfall: Option<PrecipitationAmount>,
+    /// Rapid increase of the snow depth (`SNINCR`).
+    pub snow_increasing_rapidly: Option<SnowIncrease>,
+    /// Peak wind since the last routine report (`PK WND dddff(f)/(hh)mm`).
+    pub peak_wind: Option<PeakWind>,
+    /// Wind shift (`WSHFT (hh)mm`).
+    pub wind_shift: Option<WindShift>,
+    /// Beginning and ending times of precipitation and thunderstorms (e.g. `RAB15E30SNB30`, `TSB0159E30`).
+    pub weather_events: Vec<WeatherEvent>,
+    /// Lightning, thunderstorm location and significant convective clouds.
+    pub convective_activities: Vec<ConvectiveActivity>,
+    /// Visibility observed from the control tower (`TWR VIS vvvvv`).
+    pub tower_visibility: Option<Quantity>,
+    /// Visibility observed at the surface (`SFC VIS vvvvv`).
+    pub surface_visibility: Option<Quantity>,
+    /// Variable prevailing visibility (`VIS vvvvvVvvvvv`).
+    pub variable_visibility: Option<Quantity>,
+    /// Visibility at a second location (`VIS vvvvv RWYdd`).
+    pub second_site_visibility: Option<SecondSiteQuantity>,
+    /// Variable ceiling height (`CIG hhhVhhh`).
+    pub variable_ceiling: Option<Quantity>,
+    /// Ceiling height at a second location (`CIG hhh RWYdd`).
+    pub second_site_ceiling: Option<SecondSiteQuantity>,
+    /// Types and opacities of the layers in Canadian reports, from the lowest layer upwards (e.g. `SC6AC2`).
+    pub cloud_opacities: Vec<CloudOpacity>,
+    /// Density altitude (`DENSITY ALT`).
+    pub density_altitude: Option<Quantity>,
+    /// Diameter of the largest hailstone, in inches in the United States (`GR 1 3/4`), otherwise in metric units (e.g. `GR 15MM`).
+    pub hail_size: Option<Quantity>,
+    /// Height of the cloud base in metres (`QBBhhh`), Russia only.
+    pub cloud_base: Option<Quantity>,
+    /// Pressure at the aerodrome elevation (`QFEppp/pppp`), in hectopascals if given, otherwise
+    /// in millimetres of mercury.
+    pub station_pressure: Option<Quantity>,
+    /// Flag if the mountains are obscured (`MT OBSC`).
+    pub are_mountains_obscured: bool,
+    /// Cloud layers with their amounts in oktas (e.g. `1CU020 3SC035`), Japan only.
+    pub okta_cloud_layers: Vec<OktaCloudLayer>,
+    /// Maximum and minimum temperatures during the last 6 hours (`1snTTT`, `2snTTT`).
+    pub six_hour_temperature_extremes: TemperatureExtremes,
+    /// Maximum and minimum temperatures during the last 24 hours (`4snTxTxTxsnTnTnTn`).
+    pub twenty_four_hour_temperature_extremes: Option<TemperatureExtremes>,
+    /// Pressure tendency during the last 3 hours (`5appp`).
+    pub pressure_tendency: Option<PressureTendency>,
+    /// Rapid pressure change (`PRESRR`, `PRESFR`).
+    pub rapid_pressure_change: Option<RapidPressureChange>,
+    /// Decoded remark groups in the order of their appearance.
+    pub items: Vec<Remark>,
+    /// Remark groups which could not be decoded.
+    pub unparsed: Vec<String>,
+}
+
+impl Remarks {
+    pub(super) fn push_raw(&mut self, text: &str) {
+        let text = text.trim();
+
+        if text.is_empty() {
+            return;
+        }
+
+        self.raw = match self.raw.take() {
+            Some(raw) => Some(format!("{} {}", raw, text)),
+            None => Some(text.to_string()),
+        };
+    }
+}
+
+pub(super) fn handle_station_operation(text: &str) -> Option<(Remark, usize)> {
+    STATION_OPERATION_RE.captures(text)
+        .map(|capture| {
+            let remark = Remark::from_str(&capture["operation"]).unwrap();
+
+            let end = capture.name("end").unwrap().end();
+
+            (remark, end)
+        })
+}
+
+pub(super) fn handle_automated_station_type(text: &str) -> Option<(AutomatedStationType, usize)> {
+    AUTOMATED_STATION_TYPE_RE.captures(text)
+        .map(|capture| {
+            let station_type = AutomatedStationType::from_str(&capture["station_type"]).unwrap();
+
+            let end = capture.name("end").unwrap().end();
+
+            (station_type, end)
+        })
+}
+
+/// Sea-level pressure with the hundreds of hectopascals (9xx or 10xx) restored to the reported
+/// `hectopascals` below 100 hPa (e.g. 13.2 for `SLP132`).
+///
+/// The hundreds are inferred from the pressure reported in the main section if available.
+/// Otherwise, values below 50.0 hPa are considered to be above 1000 hPa.
+fn infer_sea_level_pressure(hectopascals: Number, pressure: &Pressure) -> Number {
+    let low = hectopascals + 900;
+    let high = hectopascals + 1000;
+
+    let reference_hpa = pressure.pressure.and_then(|p| match (p.value, p.units) {
+        (Value::Exact(x), Unit::HectoPascal) => Some(x.as_f64()),
+        (Value::Exact(x), Unit::InchOfMercury) => Some(x.as_f64() * 33.8639),
+        _ => None,
+    });
+
+    match reference_hpa {
+        Some(reference) if (low.as_f64() - reference).abs() < (high.as_f64() - reference).abs() => low,
+        Some(_) => high,
+        None if hectopascals < Number::from(50) => high,
+        None => low,
+    }
+}
+
+pub(super) fn handle_sea_level_pressure(text: &str, pressure: &Pressure) -> Option<(Pressure, usize)> {
+    SEA_LEVEL_PRESSURE_RE.captures(text)
+        .and_then(|capture| {
+            let (sea_level_pressure, pressure_nil_reason) = match &capture["pressure"] {
+                "NO" => (None, Some(NilReason::Missing)),
+                s => {
+                    let mut slp = Quantity::parse(s, Unit::HectoPascal).ok()? / 10;
+
+                    if let Value::Exact(hectopascals) = slp.value {
+                        slp.value = Value::Exact(infer_sea_level_pressure(hectopascals, pressure));
+                    }
+
+                    (Some(slp), None)
+                },
+            };
+
+            let end = capture.name("end").unwrap().end();
+
+            let sea_level_pressure = Pressure { pressure: sea_level_pressure, pressure_nil_reason };
+
+            Some((sea_level_pressure, end))
+        })
+}
+
+fn parse_precise_temperature(s: &str) -> Option<Quantity> {
+    let sign = if s.starts_with('1') { "-" } else { "" };
+
+    Some(Quantity::parse(&format!("{}{}", sign, &s[1..]), Unit::DegreeCelsius).ok()? / 10)
+}
+
+pub(super) fn handle_precise_temperature(text: &str) -> Option<(Temperature, usize)> {
+    PRECISE_TEMPERATURE_RE.captures(text)
+        .and_then(|capture| {
+            let temperature = Some(parse_precise_temperature(&capture["temperature"])?);
+
+            let (dew_point, dew_point_nil_reason) = match capture.name("dew_point").map(|c| c.as_str()) {
+                Some("////") => (None, Some(NilReason::Missing)),
+                Some(s) => (Some(parse_precise_temperature(s)?), None),
+                None => (None, Some(NilReason::NotReported)),
+            };
+
+            let end = capture.name("end").unwrap().end();
+
+            let temperature = Temperature { temperature, dew_point, temperature_nil_reason: None, dew_point_nil_reason };
+
+            Some((temperature, end))
+        })
+}
+
+/// Parses a precipitation amount in hundredths or tenths of an inch, as given by `divisor`.
+///
+/// Zero amount in hundredths of an inch stands for a trace of precipitation.
+fn parse_precipitation_amount(s: &str, divisor: i64) -> Option<PrecipitationAmount> {
+    if s.chars().all(|c| c == '/') {
+        return Some(PrecipitationAmount { amount: None, is_trace: false, amount_nil_reason: Some(NilReason::Missing) });
+    }
+
+    let amount = Quantity::parse(s, Unit::Inch).ok()? / divisor;
+
+    if divisor == 100 && amount.value == Value::Exact(Number::from(0)) {
+        let trace = Quantity { value: Value::Below(Number::new(1, divisor).unwrap()), ..amount };
+        Some(PrecipitationAmount { amount: Some(trace), is_trace: true, amount_nil_reason: None })
+    } else {
+        Some(PrecipitationAmount { amount: Some(amount), is_trace: false, amount_nil_reason: None })
+    }
+}
+
+fn handle_precipitation_amount(precipitation_re: &Regex, text: &str, divisor: i64) -> Option<(PrecipitationAmount, usize)> {
+    precipitation_re.captures(text)
+        .and_then(|capture| {
+            let precipitation = parse_precipitation_amount(&capture["amount"], divisor)?;
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((precipitation, end))
+        })
+}
+
+pub(super) fn handle_hourly_precipitation(text: &str) -> Option<(PrecipitationAmount, usize)> {
+    handle_precipitation_amount(&HOURLY_PRECIPITATION_RE, text, 100)
+}
+
+pub(super) fn handle_three_or_six_hour_precipitation(text: &str) -> Option<(PrecipitationAmount, usize)> {
+    handle_precipitation_amount(&THREE_OR_SIX_HOUR_PRECIPITATION_RE, text, 100)
+}
+
+pub(super) fn handle_twenty_four_hour_precipitation(text: &str) -> Option<(PrecipitationAmount, usize)> {
+    handle_precipitation_amount(&TWENTY_FOUR_HOUR_PRECIPITATION_RE, text, 100)
+}
+
+pub(super) fn handle_six_hour_snowfall(text: &str) -> Option<(PrecipitationAmount, usize)> {
+    handle_precipitation_amount(&SIX_HOUR_SNOWFALL_RE, text, 10)
+}
+
+pub(super) fn handle_snow_depth(text: &str) -> Option<(Quantity, usize)> {
+    SNOW_DEPTH_RE.captures(text)
+        .and_then(|capture| {
+            let snow_depth = Quantity::parse(&capture["depth"], Unit::Inch).ok()?;
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((snow_depth, end))
+        })
+}
+
+/// Parses the units of the metric snow depth and hail size groups.
+fn parse_metric_units(s: &str) -> Unit {
+    match s {
+        "CM" => Unit::Centimetre,
+        _ => Unit::Millimetre,
+    }
+}
+
+pub(super) fn handle_metric_snow_depth(text: &str) -> Option<(Quantity, usize)> {
+    METRIC_SNOW_DEPTH_RE.captures(text)
+        .and_then(|capture| {
+            let snow_depth = Quantity::parse(&capture["depth"], parse_metric_units(&capture["units"])).ok()?;
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((snow_depth, end))
+        })
+}
+
+pub(super) fn handle_snow_water_equivalent(text: &str) -> Option<(Quantity, usize)> {
+    SNOW_WATER_EQUIVALENT_RE.captures(text)
+        .and_then(|capture| {
+            let water_equivalent = Quantity::parse(&capture["amount"], Unit::Inch).ok()? / 10;
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((water_equivalent, end))
+        })
+}
+
+pub(super) fn handle_snow_increasing_rapidly(text: &str) -> Option<(SnowIncrease, usize)> {
+    SNOW_INCREASING_RAPIDLY_RE.captures(text)
+        .and_then(|capture| {
+            let hourly_increase = Quantity::parse(&capture["increase"], Unit::Inch).ok()?;
+            let snow_depth = Quantity::parse(&capture["depth"], Unit::Inch).ok()?;
+
+            let end = capture.name("end").unwrap().end();
+
+            let snow_increase = SnowIncrease { hourly_increase, snow_depth };
+
+            Some((snow_increase, end))
+        })
+}
+
+fn handle_six_hour_temperature(temperature_re: &Regex, text: &str) -> Option<(Quantity, usize)> {
+    temperature_re.captures(text)
+        .and_then(|capture| {
+            let temperature = parse_precise_temperature(&capture["temperature"])?;
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((temperature, end))
+        })
+}
+
+pub(super) fn handle_six_hour_maximum_temperature(text: &str) -> Option<(Quantity, usize)> {
+    handle_six_hour_temperature(&SIX_HOUR_MAXIMUM_TEMPERATURE_RE, text)
+}
+
+pub(super) fn handle_six_hour_minimum_temperature(text: &str) -> Option<(Quantity, usize)> {
+    handle_six_hour_temperature(&SIX_HOUR_MINIMUM_TEMPERATURE_RE, text)
+}
+
+pub(super) fn handle_twenty_four_hour_temperature(text: &str) -> Option<(TemperatureExtremes, usize)> {
+    TWENTY_FOUR_HOUR_TEMPERATURE_RE.captures(text)
+        .and_then(|capture| {
+            let maximum_temperature = Some(parse_precise_temperature(&capture["maximum"])?);
+            let minimum_temperature = Some(parse_precise_temperature(&capture["minimum"])?);
+
+            let end = capture.name("end").unwrap().end();
+
+            let extremes = TemperatureExtremes { maximum_temperature, minimum_temperature };
+
+            Some((extremes, end))
+        })
+}
+
+pub(super) fn handle_pressure_tendency(text: &str) -> Option<(PressureTendency, usize)> {
+    PRESSURE_TENDENCY_RE.captures(text)
+        .and_then(|capture| {
+            let characteristic = PressureCharacteristic::from_str(&capture["characteristic"]).unwrap();
+
+            let mut pressure_change = Quantity::parse(&capture["change"], Unit::HectoPascal).ok()? / 10;
+
+            if let (Value::Exact(x), "5" | "6" | "7" | "8") = (pressure_change.value, &capture["characteristic"]) {
+                // pressure lower than 3 hours ago
+                pressure_change.value = Value::Exact(-x);
+            }
+
+            let end = capture.name("end").unwrap().end();
+
+            let tendency = PressureTendency { characteristic, pressure_change };
+
+            Some((tendency, end))
+        })
+}
+
+pub(super) fn handle_rapid_pressure_change(text: &str) -> Option<(RapidPressureChange, usize)> {
+    RAPID_PRESSURE_CHANGE_RE.captures(text)
+        .map(|capture| {
+            let change = RapidPressureChange::from_str(&capture["change"]).unwrap();
+
+            let end = capture.name("end").unwrap().end();
+
+            (change, end)
+        })
+}
+
+/// Resolves the time of a remark group given as `hhmm` or minutes only (`mm`).
+///
+/// Minutes only refer to the hour of the observation, or to the previous hour if they are later than the observation.
+/// The time is converted to a datetime or a day and time like the observation time using the anchor `policy`,
+/// see [MetarTime::to_date_time()].
+fn resolve_time(hour: Option<&str>, minute: &str, observation_time: Option<&MetarTime>, policy: AnchorPolicy) -> Option<MetarTime> {
+    let minute = minute.parse().ok()?;
+
+    let observation_naive_time = observation_time.map(|t| match t {
+        MetarTime::DateTime(utc_dt) => utc_dt.0.time(),
+        MetarTime::DayTime(utc_d_t) => utc_d_t.1,
+        MetarTime::Time(utc_t) => utc_t.0,
+    });
+
+    let hour = match hour {
+        Some(h) => h.parse().ok()?,
+        None => {
+            let ont = observation_naive_time?;
+
+            if minute <= ont.minute() {
+                ont.hour()
+            } else {
+                (ont.hour() + 23) % 24
+            }
+        },
+    };
+
+    let naive_time = NaiveTime::from_hms_opt(hour, minute, 0)?;
+    let time = MetarTime::Time(UtcTime(naive_time));
+
+    match observation_time {
+        Some(MetarTime::DateTime(utc_dt)) => time.to_date_time(utc_dt.0, policy),
+        Some(MetarTime::DayTime(utc_d_t)) => Some(to_day_time(naive_time, utc_d_t, policy).unwrap_or(time)),
+        _ => Some(time),
+    }
+}
+
+/// Converts the time into a day and time near the observation, [None] if the day depends on the unknown month,
+/// e.g. the day before the 1st.
+fn to_day_time(time: NaiveTime, observation: &UtcDayTime, policy: AnchorPolicy) -> Option<MetarTime> {
+    // any month with 31 days serves as the anchor, the days up to the 28th exist in all months
+    let anchor = NaiveDate::from_ymd_opt(2000, 1, observation.0)?.and_time(observation.1);
+
+    match MetarTime::Time(UtcTime(time)).to_date_time(anchor, policy)? {
+        MetarTime::DateTime(UtcDateTime(dt)) if dt.month() == 1 && dt.day() <= observation.0.max(28) => {
+            Some(MetarTime::DayTime(UtcDayTime(dt.day(), time)))
+        },
+        _ => None,
+    }
+}
+
+pub(super) fn handle_peak_wind(text: &str, observation_time: Option<&MetarTime>, policy: AnchorPolicy) -> Option<(PeakWind, usize)> {
+    PEAK_WIND_RE.captures(text)
+        .and_then(|capture| {
+            let wind_from_direction = Quantity::parse_direction(&capture["direction"]).ok()?;
+            let wind_speed = Quantity::parse(&capture["speed"], Unit::Knot).ok()?;
+
+            let time = resolve_time(capture.name("hour").map(|c| c.as_str()), &capture["minute"], observation_time, policy);
+
+            let end = capture.name("end").unwrap().end();
+
+            let peak_wind = PeakWind { wind_from_direction, wind_speed, time };
+
+            Some((peak_wind, end))
+        })
+}
+
+pub(super) fn handle_wind_shift(text: &str, observation_time: Option<&MetarTime>, policy: AnchorPolicy) -> Option<(WindShift, usize)> {
+    WIND_SHIFT_RE.captures(text)
+        .map(|capture| {
+            let time = resolve_time(capture.name("hour").map(|c| c.as_str()), &capture["minute"], observation_time, policy);
+            let is_frontal_passage = capture.name("fropa").is_some();
+
+            let end = capture.name("end").unwrap().end();
+
+            let wind_shift = WindShift { time, is_frontal_passage };
+
+            (wind_shift, end)
+        })
+}
+
+pub(super) fn handle_weather_events(text: &str, observation_time: Option<&MetarTime>, policy: AnchorPolicy) -> Option<(Vec<WeatherEvent>, usize)> {
+    WEATHER_EVENTS_RE.captures(text)
+        .map(|capture| {
+            let mut events: Vec<WeatherEvent> = Vec::new();
+
+            for event_capture in WEATHER_EVENT_RE.captures_iter(&capture["events"]) {
+                let mut descriptors = Vec::new();
+                let mut phenomena = Vec::new();
+
+                for group in event_capture["code"].chars().collect::<Vec<_>>().chunks(2).map(String::from_iter) {
+                    if let Ok(wd) = WeatherDescriptor::from_str(&group) {
+                        descriptors.push(wd);
+                    } else if let Ok(wp) = WeatherPhenomena::from_str(&group) {
+                        phenomena.push(wp);
+                    }
+                }
+
+                // index of the first event of the current weather
+                let first_idx = events.len();
+
+                for time_capture in WEATHER_EVENT_TIME_RE.captures_iter(&event_capture["times"]) {
+                    let time = resolve_time(time_capture.name("hour").map(|c| c.as_str()), &time_capture["minute"], observation_time, policy);
+
+                    let is_open = events.len() > first_idx && events.last().is_some_and(|e| e.end_time.is_none());
+
+                    match &time_capture["indicator"] {
+                        "E" if is_open => events.last_mut().unwrap().end_time = time,
+                        "E" => events.push(WeatherEvent { descriptors: descriptors.clone(), phenomena: phenomena.clone(), begin_time: None, end_time: time }),
+                        _ => events.push(WeatherEvent { descriptors: descriptors.clone(), phenomena: phenomena.clone(), begin_time: time, end_time: None }),
+                    }
+                }
+            }
+
+            let end = capture.name("end").unwrap().end();
+
+            (events, end)
+        })
+}
+
+/// Parses octants like `NE`, `NW-E` or `N AND SE`, [None] if any of them is invalid.
+fn parse_octants(s: &str) -> Option<Vec<DirectionOctant>> {
+    if s == "ALQDS" || s == "ALQS" {
+        return Some(OCTANTS.to_vec());
+    }
+
+    let mut octants = Vec::new();
+
+    for part in s.split(" AND ") {
+        let mut part_octants = part.split('-')
+            .map(|o| DirectionOctant::from_str(o).ok())
+            .collect::<Option<Vec<_>>>()?;
+
+        let mut from = part_octants.remove(0);
+        octants.push(from);
+
+        for to in part_octants {
+            let from_idx = OCTANTS.iter().position(|&o| o == from).unwrap();
+            let to_idx = OCTANTS.iter().position(|&o| o == to).unwrap();
+            let steps = (to_idx + 8 - from_idx) % 8;
+
+            octants.extend((1..=steps).map(|step| OCTANTS[(from_idx + step) % 8]));
+
+            from = to;
+        }
+    }
+
+    Some(octants)
+}
+
+pub(super) fn handle_convective_activity(text: &str) -> Option<(ConvectiveActivity, usize)> {
+    CONVECTIVE_ACTIVITY_RE.captures(text)
+        .and_then(|capture| {
+            let phenomenon = if capture["phenomenon"].contains("LTG") {
+                ConvectivePhenomenon::Lightning
+            } else {
+                ConvectivePhenomenon::from_str(&capture["phenomenon"]).unwrap()
+            };
+
+            let frequency = capture.name("frequency")
+                .map(|c| LightningFrequency::from_str(c.as_str()).unwrap());
+
+            let lightning_types = capture.name("types")
+                .map(|c| c.as_str().chars()
+                    .collect::<Vec<_>>()
+                    .chunks(2)
+                    .map(|chunk| LightningType::from_str(&String::from_iter(chunk)).unwrap())
+                    .collect())
+                .unwrap_or_default();
+
+            let distance = capture.name("distance")
+                .map(|c| DistanceQualifier::from_str(c.as_str()).unwrap());
+
+            let directions = match capture.name("directions") {
+                Some(c) => parse_octants(c.as_str())?,
+                None => Vec::new(),
+            };
+
+            let movement = match capture.name("movement") {
+                Some(c) => Some(DirectionOctant::from_str(c.as_str()).ok()?),
+                None => None,
+            };
+
+            let end = capture.name("end").unwrap().end();
+
+            let activity = ConvectiveActivity { phenomenon, frequency, lightning_types, distance, directions, movement };
+
+            Some((activity, end))
+        })
+}
+
+fn handle_visibility(visibility_re: &Regex, text: &str, units: Unit) -> Option<(Quantity, usize)> {
+    visibility_re.captures(text)
+        .and_then(|capture| {
+            let visibility = Quantity::parse(&capture["visibility"], units).ok()?;
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((visibility, end))
+        })
+}
+
+pub(super) fn handle_tower_visibility(text: &str, units: Unit) -> Option<(Quantity, usize)> {
+    handle_visibility(&TOWER_VISIBILITY_RE, text, units)
+}
+
+pub(super) fn handle_surface_visibility(text: &str, units: Unit) -> Option<(Quantity, usize)> {
+    handle_visibility(&SURFACE_VISIBILITY_RE, text, units)
+}
+
+pub(super) fn handle_variable_visibility(text: &str, units: Unit) -> Option<(Quantity, usize)> {
+    handle_visibility(&VARIABLE_VISIBILITY_RE, text, units)
+}
+
+pub(super) fn handle_second_site_visibility(text: &str, units: Unit) -> Option<(SecondSiteQuantity, usize)> {
+    SECOND_SITE_VISIBILITY_RE.captures(text)
+        .and_then(|capture| {
+            let quantity = Quantity::parse(&capture["visibility"], units).ok()?;
+            let runway = Runway::from_str(&capture["runway"]).ok()?;
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((SecondSiteQuantity { quantity, runway }, end))
+        })
+}
+
+pub(super) fn handle_variable_ceiling(text: &str) -> Option<(Quantity, usize)> {
+    VARIABLE_CEILING_RE.captures(text)
+        .and_then(|capture| {
+            let ceiling = Quantity::parse(&capture["ceiling"], Unit::Foot).ok()? * 100;
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((ceiling, end))
+        })
+}
+
+pub(super) fn handle_second_site_ceiling(text: &str) -> Option<(SecondSiteQuantity, usize)> {
+    SECOND_SITE_CEILING_RE.captures(text)
+        .and_then(|capture| {
+            let quantity = Quantity::parse(&capture["ceiling"], Unit::Foot).ok()? * 100;
+            let runway = Runway::from_str(&capture["runway"]).ok()?;
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((SecondSiteQuantity { quantity, runway }, end))
+        })
+}
+
+pub(super) fn handle_missing_sensor(text: &str) -> Option<(MissingSensor, usize)> {
+    MISSING_SECONDARY_SENSOR_RE.captures(text)
+        .or_else(|| MISSING_SENSOR_RE.captures(text))
+        .and_then(|capture| {
+            let sensor = Sensor::from_str(&capture["sensor"]).unwrap();
+
+            let runway = match capture.name("runway") {
+                Some(c) => Some(Runway::from_str(c.as_str()).ok()?),
+                None => None,
+            };
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((MissingSensor { sensor, runway }, end))
+        })
+}
+
+pub(super) fn handle_maintenance_indicator(text: &str) -> Option<usize> {
+    MAINTENANCE_INDICATOR_RE.captures(text)
+        .map(|capture| capture.name("end").unwrap().end())
+}
+
+pub(super) fn handle_cloud_opacities(text: &str, clouds: &[CloudLayer]) -> Option<(Vec<CloudOpacity>, usize)> {
+    CLOUD_OPACITIES_RE.captures(text)
+        .map(|capture| {
+            let mut opacities = Vec::new();
+
+            for layer_capture in CLOUD_OPACITY_RE.captures_iter(&capture["layers"]) {
+                let layer_type = &layer_capture["layer_type"];
+
+                let cloud_type = CloudType::from_str(layer_type).ok();
+                let obscuration = match cloud_type {
+                    Some(_) => None,
+                    None => WeatherPhenomena::from_str(layer_type).ok(),
+                };
+
+                let oktas = layer_capture["oktas"].parse().unwrap();
+
+                opacities.push(CloudOpacity { cloud_type, obscuration, oktas, cloud_layer_index: None });
+            }
+
+            // layers in the remark section follow the reported layers in the main section,
+            // partial obscurations however may be missing in the main section,
+            // layers reported only by solidi (`//////`) have no cover and are skipped
+            let layer_indices = clouds.iter()
+                .enumerate()
+                .filter(|(_, cl)| matches!(cl.cover, Some(CloudCover::Few | CloudCover::Scattered | CloudCover::Broken
+                    | CloudCover::Overcast | CloudCover::VerticalVisibility)))
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>();
+
+            let cloud_opacities_count = opacities.iter().filter(|o| o.cloud_type.is_some()).count();
+
+            if layer_indices.len() == opacities.len() {
+                for (opacity, i) in opacities.iter_mut().zip(layer_indices) {
+                    opacity.cloud_layer_index = Some(i);
+                }
+            } else if layer_indices.len() == cloud_opacities_count {
+                for (opacity, i) in opacities.iter_mut().filter(|o| o.cloud_type.is_some()).zip(layer_indices) {
+                    opacity.cloud_layer_index = Some(i);
+                }
+            }
+
+            let end = capture.name("end").unwrap().end();
+
+            (opacities, end)
+        })
+}
+
+pub(super) fn handle_density_altitude(text: &str) -> Option<(Quantity, usize)> {
+    DENSITY_ALTITUDE_RE.captures(text)
+        .and_then(|capture| {
+            let altitude = Quantity::parse(&capture["altitude"], Unit::Foot).ok()?;
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((altitude, end))
+        })
+}
+
+pub(super) fn handle_hail_size(text: &str) -> Option<(Quantity, usize)> {
+    HAIL_SIZE_RE.captures(text)
+        .and_then(|capture| {
+            let mut hail_size = Quantity::parse(&capture["size"], Unit::Inch).ok()?;
+
+            if let (Some(_), Value::Exact(x)) = (capture.name("below"), hail_size.value) {
+                hail_size.value = Value::Below(x);
+            }
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((hail_size, end))
+        })
+}
+
+pub(super) fn handle_metric_hail_size(text: &str) -> Option<(Quantity, usize)> {
+    METRIC_HAIL_SIZE_RE.captures(text)
+        .and_then(|capture| {
+            let hail_size = Quantity::parse(&capture["size"], parse_metric_units(&capture["units"])).ok()?;
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((hail_size, end))
+        })
+}
+
+pub(super) fn handle_cloud_base(text: &str) -> Option<(Quantity, usize)> {
+    CLOUD_BASE_RE.captures(text)
+        .and_then(|capture| {
+            let cloud_base = Quantity::parse(&capture["height"], Unit::Metre).ok()?;
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((cloud_base, end))
+        })
+}
+
+pub(super) fn handle_station_pressure(text: &str) -> Option<(Quantity, usize)> {
+    STATION_PRESSURE_RE.captures(text)
+        .and_then(|capture| {
+            let station_pressure = match capture.name("hpa") {
+                Some(c) => Quantity::parse(c.as_str(), Unit::HectoPascal).ok()?,
+                None => Quantity::parse(&capture["mmhg"], Unit::MillimetreOfMercury).ok()?,
+            };
+
+            let end = capture.name("end").unwrap().end();
+
+            Some((station_pressure, end))
+        })
+}
+
+pub(super) fn handle_mountains_obscured(text: &str) -> Option<usize> {
+    MOUNTAINS_OBSCURED_RE.captures(text)
+        .map(|capture| capture.name("end").unwrap().end())
+}
+
+pub(super) fn handle_okta_cloud_layer(text: &str) -> Option<(OktaCloudLayer, usize)> {
+    OKTA_CLOUD_LAYER_RE.captures(text)
+        .and_then(|capture| {
+            let oktas = capture["oktas"].parse().unwrap();
+            let cloud_type = CloudType::from_str(&capture["cloud"]).unwrap();
+            let height = Quantity::parse(&capture["height"], Unit::Foot).ok()? * 100;
+
+            let end = capture.name("end").unwrap().end();
+
+            let layer = OktaCloudLayer { oktas, cloud_type, height };
+
+            Some((layer, end))
+        })
+}
+
+pub(super) fn handle_custom_remark(decoders: &[Arc<dyn DynRemarkDecoder>], text: &str) -> Option<(Remark, usize)> {
+    decoders.iter()
+        .find_map(|decoder| {
+            decoder.decode_value(text)
+                // the decoder must consume a whole group
+                .filter(|(_, end)| text.get(..*end).is_some_and(|t| t.ends_with(char::is_whitespace)))
+                .map(|(value, end)| (Remark::Custom { name: decoder.name().to_string(), value }, end))
+        })
+}
+
+/// Decodes a remark group into a METAR, returns the end index of the group.
+///
+/// Arguments are the METAR, its decoder, the report text starting with the group and the index of the group in the report.
+type BuiltInHandler = fn(&mut Metar, &Decoder, &str, usize) -> Option<usize>;
+
+/// Regions of the groups decoded for all stations.
+const ALL_REGIONS: &[RemarkRegion] = &[];
+
+/// Regions of the coded groups of FMH-1.
+const UNITED_STATES: &[RemarkRegion] = &[RemarkRegion::UnitedStates];
+
+/// Regions of the coded groups of FMH-1 reported by Canadian stations as well.
+const NORTH_AMERICA: &[RemarkRegion] = &[RemarkRegion::UnitedStates, RemarkRegion::Canada];
+
+/// Built-in handler of a group reported at most once, decoded into an [Option] field.
+macro_rules! single {
+    ($root:ident $(. $field:ident)*, |$metar:pat_param, $decoder:pat_param, $text:pat_param| $decode:expr) => {
+        |metar: &mut Metar, decoder: &Decoder, text: &str, idx: usize| {
+            if metar.$root$(.$field)*.is_some() {
+                return None;
+            }
+
+            let (value, relative_end) = (|$metar: &Metar, $decoder: &Decoder, $text: &str| $decode)(metar, decoder, text)?;
+            metar.$root$(.$field)* = Some(value);
+            metar.record_span(format_args!(concat!(stringify!($root) $(, ".", stringify!($field))*)), idx..idx + relative_end);
+
+            Some(relative_end)
+        }
+    };
+}
+
+/// Built-in handler of a repeatable group, decoded into a [Vec] field.
+macro_rules! repeated {
+    ($root:ident $(. $field:ident)*, |$metar:pat_param, $decoder:pat_param, $text:pat_param| $decode:expr) => {
+        |metar: &mut Metar, decoder: &Decoder, text: &str, idx: usize| {
+            let (value, relative_end) = (|$metar: &Metar, $decoder: &Decoder, $text: &str| $decode)(metar, decoder, text)?;
+            metar.$root$(.$field)*.push(value);
+            metar.record_span(format_args!(concat!(stringify!($root) $(, ".", stringify!($field))*, "[{}]"), metar.$root$(.$field)*.len() - 1), idx..idx + relative_end);
+
+            Some(relative_end)
+        }
+    };
+}
+
+/// Built-in handler of an indicator group, decoded into a [bool] field.
+macro_rules! flag {
+    ($root:ident $(. $field:ident)*, |$text:pat_param| $decode:expr) => {
+        |metar: &mut Metar, _: &Decoder, text: &str, idx: usize| {
+            if metar.$root$(.$field)* {
+                return None;
+            }
+
+            let relative_end = (|$text: &str| $decode)(text)?;
+            metar.$root$(.$field)* = true;
+            metar.record_span(format_args!(concat!(stringify!($root) $(, ".", stringify!($field))*)), idx..idx + relative_end);
+
+            Some(relative_end)
+        }
+    };
+}
+
+/// Visibility remarks are given in the units of the prevailing visibility.
+fn visibility_units(metar: &Metar) -> Unit {
+    metar.visibility.prevailing_visibility
+        .map(|q| q.units)
+        .unwrap_or(Unit::StatuteMile)
+}
+
+/// Built-in remark groups with the regions using them, tried in the listed order.
+static BUILT_IN_HANDLERS: &[(&[RemarkRegion], BuiltInHandler)] = &[
+    (NORTH_AMERICA, single!(remarks.automated_station_type, |_, _, text| handle_automated_station_type(text))),
+    (NORTH_AMERICA, single!(remarks.sea_level_pressure, |metar, _, text| handle_sea_level_pressure(text, &metar.pressure))),
+    (NORTH_AMERICA, single!(remarks.precise_temperature, |_, _, text| handle_precise_temperature(text))),
+    (UNITED_STATES, single!(remarks.hourly_precipitation, |_, _, text| handle_hourly_precipitation(text))),
+    (UNITED_STATES, single!(remarks.three_or_six_hour_precipitation, |_, _, text| handle_three_or_six_hour_precipitation(text))),
+    (UNITED_STATES, single!(remarks.twenty_four_hour_precipitation, |_, _, text| handle_twenty_four_hour_precipitation(text))),
+    (UNITED_STATES, single!(remarks.snow_depth, |_, _, text| handle_snow_depth(text))),
+    (ALL_REGIONS, single!(remarks.snow_depth, |_, _, text| handle_metric_snow_depth(text))),
+    (UNITED_STATES, single!(remarks.snow_water_equivalent, |_, _, text| handle_snow_water_equivalent(text))),
+    (UNITED_STATES, single!(remarks.six_hour_snowfall, |_, _, text| handle_six_hour_snowfall(text))),
+    (UNITED_STATES, single!(remarks.snow_increasing_rapidly, |_, _, text| handle_snow_increasing_rapidly(text))),
+    (NORTH_AMERICA, single!(remarks.peak_wind, |metar, decoder, text| handle_peak_wind(text, metar.header.observation_time.as_ref(), decoder.options.anchor_policy))),
+    (UNITED_STATES, single!(remarks.wind_shift, |metar, decoder, text| handle_wind_shift(text, metar.header.observation_time.as_ref(), decoder.options.anchor_policy))),
+    (UNITED_STATES, |metar: &mut Metar, decoder: &Decoder, text: &str, idx: usize| {
+        let (events, relative_end) = handle_weather_events(text, metar.header.observation_time.as_ref(), decoder.options.anchor_policy)?;
+        let first_idx = metar.remarks.weather_events.len();
+        metar.remarks.weather_events.extend(events);
+
+        for event_idx in first_idx..metar.remarks.weather_events.len() {
+            metar.record_span(format_args!("remarks.weather_events[{}]", event_idx), idx..idx + relative_end);
+        }
+
+        Some(relative_end)
+    }),
+    (ALL_REGIONS, repeated!(remarks.convective_activities, |_, _, text| handle_convective_activity(text))),
+    (ALL_REGIONS, single!(remarks.tower_visibility, |metar, _, text| handle_tower_visibility(text, visibility_units(metar)))),
+    (ALL_REGIONS, single!(remarks.surface_visibility, |metar, _, text| handle_surface_visibility(text, visibility_units(metar)))),
+    (ALL_REGIONS, single!(remarks.variable_visibility, |metar, _, text| handle_variable_visibility(text, visibility_units(metar)))),
+    (ALL_REGIONS, single!(remarks.second_site_visibility, |metar, _, text| handle_second_site_visibility(text, visibility_units(metar)))),
+    (ALL_REGIONS, single!(remarks.variable_ceiling, |_, _, text| handle_variable_ceiling(text))),
+    (ALL_REGIONS, single!(remarks.second_site_ceiling, |_, _, text| handle_second_site_ceiling(text))),
+    (UNITED_STATES, single!(remarks.six_hour_temperature_extremes.maximum_temperature, |_, _, text| handle_six_hour_maximum_temperature(text))),
+    (UNITED_STATES, single!(remarks.six_hour_temperature_extremes.minimum_temperature, |_, _, text| handle_six_hour_minimum_temperature(text))),
+    (UNITED_STATES, single!(remarks.twenty_four_hour_temperature_extremes, |_, _, text| handle_twenty_four_hour_temperature(text))),
+    (NORTH_AMERICA, single!(remarks.pressure_tendency, |_, _, text| handle_pressure_tendency(text))),
+    (NORTH_AMERICA, single!(remarks.rapid_pressure_change, |_, _, text| handle_rapid_pressure_change(text))),
+    (NORTH_AMERICA, |metar: &mut Metar, _: &Decoder, text: &str, idx: usize| {
+        if !metar.remarks.cloud_opacities.is_empty() {
+            return None;
+        }
+
+        let (opacities, relative_end) = handle_cloud_opacities(text, &metar.clouds)?;
+        metar.remarks.cloud_opacities = opacities;
+        metar.record_span(format_args!("remarks.cloud_opacities"), idx..idx + relative_end);
+
+        Some(relative_end)
+    }),
+    (ALL_REGIONS, single!(remarks.density_altitude, |_, _, text| handle_density_altitude(text))),
+    (UNITED_STATES, single!(remarks.hail_size, |_, _, text| handle_hail_size(text))),
+    (ALL_REGIONS, single!(remarks.hail_size, |_, _, text| handle_metric_hail_size(text))),
+    (&[RemarkRegion::Russia], single!(remarks.cloud_base, |_, _, text| handle_cloud_base(text))),
+    (ALL_REGIONS, single!(remarks.station_pressure, |_, _, text| handle_station_pressure(text))),
+    (ALL_REGIONS, flag!(remarks.are_mountains_obscured, |text| handle_mountains_obscured(text))),
+    (&[RemarkRegion::Japan], repeated!(remarks.okta_cloud_layers, |_, _, text| handle_okta_cloud_layer(text))),
+    (UNITED_STATES, repeated!(sensor_status.missing_sensors, |_, _, text| handle_missing_sensor(text))),
+    (UNITED_STATES, flag!(sensor_status.is_maintenance_needed, |text| handle_maintenance_indicator(text))),
+    (ALL_REGIONS, repeated!(remarks.items, |_, _, text| handle_station_operation(text))),
+    (ALL_REGIONS, repeated!(remarks.items, |_, decoder, text| handle_custom_remark(&decoder.remark_decoders, text))),
+];
+
+/// Tries the built-in remark groups of the region on the group at the given index.
+/// Returns the end index of the decoded group.
+pub(super) fn handle_built_in(metar: &mut Metar, decoder: &Decoder, region: Option<RemarkRegion>, text: &str, idx: usize) -> Option<usize> {
+    BUILT_IN_HANDLERS.iter()
+        .filter(|(regions, _)| regions.is_empty() || region.is_some_and(|r| regions.contains(&r)))
+        .find_map(|(_, handle)| handle(metar, decoder, text, idx))
+}
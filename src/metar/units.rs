@@ -0,0 +1,238 @@
+//! Module for the normalization of units.
+//!
+//! Reports use different units depending on the region, e.g. the wind speed in knots or metres per second
+//! and the pressure in hectopascals or inches of mercury. Normalization converts the quantities of a decoded report
+//! into a single unit system, enabled by [DecodeOptions::units()](super::DecodeOptions::units).
+//! Conversions are exact, see [Number].
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+use serde::{Serialize, Deserialize};
+
+use crate::number::Number;
+
+use super::{
+    remark::{PrecipitationAmount, SecondSiteQuantity},
+    CloudLayer, Metar, Quantity, Unit, Visibility, Wind,
+};
+
+/// Unit system of the normalization.
+///
+/// Directions and temperatures are the same in all unit systems.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitSystem {
+    /// Speeds in metres per second, distances and heights in metres, pressures in hectopascals,
+    /// precipitation amounts, snow water equivalents and hail sizes in millimetres,
+    /// and snow depths and snowfall amounts in centimetres.
+    Metric,
+    /// Speeds in knots, visibilities in statute miles, runway visual ranges and heights in feet,
+    /// pressures in inches of mercury, and precipitation amounts, snow depths and hail sizes in inches,
+    /// as used in the United States.
+    UnitedStates,
+}
+
+impl FromStr for UnitSystem {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "metric" => Ok(UnitSystem::Metric),
+            "united-states" => Ok(UnitSystem::UnitedStates),
+            _ => Err(anyhow!("Invalid unit system, given {}", s))
+        }
+    }
+}
+
+/// Kind of a quantity which determines its unit in a unit system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuantityKind {
+    Speed,
+    Visibility,
+    VisualRange,
+    Height,
+    Pressure,
+    Precipitation,
+    /// Snow depth and snowfall amount.
+    SnowDepth,
+}
+
+impl UnitSystem {
+    fn unit(&self, kind: QuantityKind) -> Unit {
+        match (self, kind) {
+            (UnitSystem::Metric, QuantityKind::Speed) => Unit::MetrePerSecond,
+            (UnitSystem::Metric, QuantityKind::Visibility | QuantityKind::VisualRange | QuantityKind::Height) => Unit::Metre,
+            (UnitSystem::Metric, QuantityKind::Pressure) => Unit::HectoPascal,
+            (UnitSystem::Metric, QuantityKind::Precipitation) => Unit::Millimetre,
+            (UnitSystem::Metric, QuantityKind::SnowDepth) => Unit::Centimetre,
+            (UnitSystem::UnitedStates, QuantityKind::Speed) => Unit::Knot,
+            (UnitSystem::UnitedStates, QuantityKind::Visibility) => Unit::StatuteMile,
+            (UnitSystem::UnitedStates, QuantityKind::VisualRange | QuantityKind::Height) => Unit::Foot,
+            (UnitSystem::UnitedStates, QuantityKind::Pressure) => Unit::InchOfMercury,
+            (UnitSystem::UnitedStates, QuantityKind::Precipitation | QuantityKind::SnowDepth) => Unit::Inch,
+        }
+    }
+}
+
+/// Physical dimension of a unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    Speed,
+    Length,
+    Pressure,
+}
+
+/// Returns the dimension of the unit and the factor converting it into the base unit of the dimension,
+/// i.e. metre per second, metre or hectopascal. [None] for directions and temperatures.
+fn base_factor(unit: Unit) -> Option<(Dimension, Number)> {
+    let (dimension, numerator, denominator) = match unit {
+        Unit::Knot => (Dimension::Speed, 1852, 3600),
+        Unit::MetrePerSecond => (Dimension::Speed, 1, 1),
+        Unit::KiloMetre => (Dimension::Length, 1000, 1),
+        Unit::Metre => (Dimension::Length, 1, 1),
+        Unit::StatuteMile => (Dimension::Length, 1609344, 1000),
+        Unit::Foot => (Dimension::Length, 3048, 10000),
+        Unit::Inch => (Dimension::Length, 254, 10000),
+        Unit::Centimetre => (Dimension::Length, 1, 100),
+        Unit::Millimetre => (Dimension::Length, 1, 1000),
+        Unit::HectoPascal => (Dimension::Pressure, 1, 1),
+        Unit::InchOfMercury => (Dimension::Pressure, 3386389, 100000),
+        Unit::MillimetreOfMercury => (Dimension::Pressure, 133322387415, 100000000000),
+        Unit::DegreeTrue | Unit::DegreeCelsius => return None,
+    };
+
+    Number::new(numerator, denominator).map(|factor| (dimension, factor))
+}
+
+impl Quantity {
+    /// Converts the quantity into the given units, [None] if the units are of a different dimension.
+    ///
+    /// Example:
+    /// ```
+    /// use rweather_decoder::metar::{decode_metar, Unit};
+    ///
+    /// let metar = decode_metar("KXYZ 121600Z 27010KT 10SM FEW030 24/14 A2992", None).unwrap();
+    /// let pressure = metar.pressure.pressure.unwrap().to_units(Unit::HectoPascal).unwrap();
+    ///
+    /// assert_eq!(pressure.value.as_f64(), Some(1013.2075888));
+    /// ```
+    pub fn to_units(&self, units: Unit) -> Option<Quantity> {
+        if self.units == units {
+            return Some(*self);
+        }
+
+        let (from_dimension, from_factor) = base_factor(self.units)?;
+        let (to_dimension, to_factor) = base_factor(units)?;
+
+        if from_dimension != to_dimension {
+            return None;
+        }
+
+        let factor = from_factor / to_factor;
+
+        Some(Quantity { value: self.value * factor, units, resolution: self.resolution.map(|r| r * factor) })
+    }
+}
+
+fn normalize(quantity: Option<&mut Quantity>, kind: QuantityKind, system: UnitSystem) {
+    if let Some(quantity) = quantity {
+        if let Some(converted) = quantity.to_units(system.unit(kind)) {
+            *quantity = converted;
+        }
+    }
+}
+
+fn normalize_wind(wind: &mut Wind, system: UnitSystem) {
+    normalize(wind.wind_speed.as_mut(), QuantityKind::Speed, system);
+    normalize(wind.wind_gust.as_mut(), QuantityKind::Speed, system);
+}
+
+fn normalize_visibility(visibility: &mut Visibility, system: UnitSystem) {
+    normalize(visibility.prevailing_visibility.as_mut(), QuantityKind::Visibility, system);
+    normalize(visibility.minimum_visibility.as_mut(), QuantityKind::Visibility, system);
+
+    for directional_visibility in visibility.directional_visibilites.iter_mut() {
+        normalize(Some(&mut directional_visibility.visibility), QuantityKind::Visibility, system);
+    }
+}
+
+fn normalize_clouds(clouds: &mut [CloudLayer], system: UnitSystem) {
+    for cloud in clouds.iter_mut() {
+        normalize(cloud.height.as_mut(), QuantityKind::Height, system);
+    }
+}
+
+fn normalize_precipitation(precipitation: Option<&mut PrecipitationAmount>, kind: QuantityKind, system: UnitSystem) {
+    normalize(precipitation.and_then(|p| p.amount.as_mut()), kind, system);
+}
+
+fn normalize_second_site(second_site: Option<&mut SecondSiteQuantity>, kind: QuantityKind, system: UnitSystem) {
+    normalize(second_site.map(|s| &mut s.quantity), kind, system);
+}
+
+impl Metar {
+    /// Converts the quantities of the report into the given unit system.
+    ///
+    /// Groups decoded by registered group handlers and custom remark decoders are kept as reported.
+    pub fn normalize_units(&mut self, system: UnitSystem) {
+        normalize_wind(&mut self.wind, system);
+        normalize_visibility(&mut self.visibility, system);
+
+        for runway_visual_range in self.runway_visual_ranges.iter_mut() {
+            normalize(Some(&mut runway_visual_range.visual_range), QuantityKind::VisualRange, system);
+        }
+
+        normalize_clouds(&mut self.clouds, system);
+        normalize(self.pressure.pressure.as_mut(), QuantityKind::Pressure, system);
+
+        for wind_shear in self.wind_shears.iter_mut() {
+            normalize(wind_shear.height.as_mut(), QuantityKind::Height, system);
+            normalize(wind_shear.wind_speed.as_mut(), QuantityKind::Speed, system);
+        }
+
+        normalize(self.sea.wave_height.as_mut(), QuantityKind::Height, system);
+
+        for trend_change in self.trend_changes.iter_mut() {
+            normalize_wind(&mut trend_change.wind, system);
+            normalize_visibility(&mut trend_change.visibility, system);
+            normalize_clouds(&mut trend_change.clouds, system);
+        }
+
+        let remarks = &mut self.remarks;
+
+        normalize(remarks.sea_level_pressure.as_mut().and_then(|p| p.pressure.as_mut()), QuantityKind::Pressure, system);
+        normalize_precipitation(remarks.hourly_precipitation.as_mut(), QuantityKind::Precipitation, system);
+        normalize_precipitation(remarks.three_or_six_hour_precipitation.as_mut(), QuantityKind::Precipitation, system);
+        normalize_precipitation(remarks.twenty_four_hour_precipitation.as_mut(), QuantityKind::Precipitation, system);
+        normalize_precipitation(remarks.six_hour_snowfall.as_mut(), QuantityKind::SnowDepth, system);
+        normalize(remarks.snow_depth.as_mut(), QuantityKind::SnowDepth, system);
+        normalize(remarks.snow_water_equivalent.as_mut(), QuantityKind::Precipitation, system);
+
+        if let Some(snow_increase) = remarks.snow_increasing_rapidly.as_mut() {
+            normalize(Some(&mut snow_increase.hourly_increase), QuantityKind::SnowDepth, system);
+            normalize(Some(&mut snow_increase.snow_depth), QuantityKind::SnowDepth, system);
+        }
+
+        normalize(remarks.peak_wind.as_mut().map(|w| &mut w.wind_speed), QuantityKind::Speed, system);
+        normalize(remarks.tower_visibility.as_mut(), QuantityKind::Visibility, system);
+        normalize(remarks.surface_visibility.as_mut(), QuantityKind::Visibility, system);
+        normalize(remarks.variable_visibility.as_mut(), QuantityKind::Visibility, system);
+        normalize_second_site(remarks.second_site_visibility.as_mut(), QuantityKind::Visibility, system);
+        normalize(remarks.variable_ceiling.as_mut(), QuantityKind::Height, system);
+        normalize_second_site(remarks.second_site_ceiling.as_mut(), QuantityKind::Height, system);
+        normalize(remarks.density_altitude.as_mut(), QuantityKind::Height, system);
+        normalize(remarks.hail_size.as_mut(), QuantityKind::Precipitation, system);
+        normalize(remarks.cloud_base.as_mut(), QuantityKind::Height, system);
+        normalize(remarks.station_pressure.as_mut(), QuantityKind::Pressure, system);
+
+        for layer in remarks.okta_cloud_layers.iter_mut() {
+            normalize(Some(&mut layer.height), QuantityKind::Height, system);
+        }
+
+        normalize(remarks.pressure_tendency.as_mut().map(|t| &mut t.pressure_change), QuantityKind::Pressure, system);
+    }
+}
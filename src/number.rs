@@ -0,0 +1,347 @@
+//! Module for handling exact numeric representations.
+
+use std::{cmp::Ordering, fmt, ops::{Add, Div, Mul, Neg}, str::FromStr};
+
+use anyhow::{anyhow, Error, Result};
+use serde::{de, Serialize, Deserialize, Deserializer};
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a.abs()
+}
+
+/// Exact rational number.
+///
+/// Reported values like `1 3/4` or `2992` hundredths of an inch of mercury are kept exactly,
+/// without any rounding errors of floating-point numbers. The number is always stored in its lowest terms.
+///
+/// The arithmetic operators never panic on overflow (except for the division by zero), a result out of the range
+/// of [i64] is approximated by the nearest representable fraction or saturated. Use [Number::checked_add],
+/// [Number::checked_mul] and [Number::checked_div] to detect the overflow.
+///
+/// JSON representation is always the nearest number, which is exact for decimal numbers.
+/// Other numbers are restored exactly by [Number::from_f64()] when deserialized, e.g. 1/3 from `0.3333333333333333`. Example:
+/// ```json
+/// 1.75
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Number {
+    numerator: i64,
+    denominator: i64,
+}
+
+impl Number {
+    /// Creates a new number `numerator / denominator`, [None] if the denominator is zero
+    /// or the number in its lowest terms is out of the range of [i64].
+    pub fn new(numerator: i64, denominator: i64) -> Option<Number> {
+        Number::from_i128(numerator as i128, denominator as i128)
+    }
+
+    fn from_i128(numerator: i128, denominator: i128) -> Option<Number> {
+        if denominator == 0 {
+            return None;
+        }
+
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+
+        Some(Number {
+            numerator: i64::try_from(numerator / divisor).ok()?,
+            denominator: i64::try_from(denominator / divisor).ok()?,
+        })
+    }
+
+    /// Creates the nearest representable number to `numerator / denominator`, saturated to the range of [i64].
+    fn approximate_i128(numerator: i128, denominator: i128) -> Number {
+        if let Some(number) = Number::from_i128(numerator, denominator) {
+            return number;
+        }
+
+        let (mut numerator, mut denominator) = (numerator * denominator.signum(), denominator.abs());
+
+        // drop the least significant bits of both terms until they fit
+        while i64::try_from(numerator).is_err() || i64::try_from(denominator).is_err() {
+            if denominator == 1 {
+                let numerator = if numerator > 0 { i64::MAX } else { i64::MIN };
+
+                return Number { numerator, denominator: 1 };
+            }
+
+            numerator >>= 1;
+            denominator = (denominator >> 1).max(1);
+        }
+
+        Number::from_i128(numerator, denominator).unwrap()
+    }
+
+    /// Checked addition, [None] if the result is out of the range of [i64].
+    pub fn checked_add(self, rhs: Number) -> Option<Number> {
+        let (numerator, denominator) = self.add_i128(rhs);
+
+        Number::from_i128(numerator, denominator)
+    }
+
+    /// Checked multiplication, [None] if the result is out of the range of [i64].
+    pub fn checked_mul(self, rhs: Number) -> Option<Number> {
+        let (numerator, denominator) = self.mul_i128(rhs);
+
+        Number::from_i128(numerator, denominator)
+    }
+
+    /// Checked division, [None] if `rhs` is zero or the result is out of the range of [i64].
+    pub fn checked_div(self, rhs: Number) -> Option<Number> {
+        let (numerator, denominator) = self.div_i128(rhs);
+
+        Number::from_i128(numerator, denominator)
+    }
+
+    fn add_i128(self, rhs: Number) -> (i128, i128) {
+        let (a, b) = (self.numerator as i128, self.denominator as i128);
+        let (c, d) = (rhs.numerator as i128, rhs.denominator as i128);
+
+        // the products of two i64 values always fit into i128, the sum of two of them as well
+        (a * d + c * b, b * d)
+    }
+
+    fn mul_i128(self, rhs: Number) -> (i128, i128) {
+        (self.numerator as i128 * rhs.numerator as i128, self.denominator as i128 * rhs.denominator as i128)
+    }
+
+    fn div_i128(self, rhs: Number) -> (i128, i128) {
+        (self.numerator as i128 * rhs.denominator as i128, self.denominator as i128 * rhs.numerator as i128)
+    }
+
+    pub fn numerator(&self) -> i64 {
+        self.numerator
+    }
+
+    /// Always positive.
+    pub fn denominator(&self) -> i64 {
+        self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    /// Converts the number into the nearest [f64].
+    pub fn as_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    /// Creates the simplest number, i.e. with the smallest denominator, which converts into the given [f64].
+    ///
+    /// Numbers converted by [Number::as_f64()] are restored exactly unless their terms are very large.
+    /// Returns [None] if no such number is representable, e.g. for infinity.
+    ///
+    /// Example:
+    /// ```
+    /// use rweather_decoder::number::Number;
+    ///
+    /// assert_eq!(Number::from_f64(1.0 / 3.0), Number::new(1, 3));
+    /// assert_eq!(Number::from_f64(0.1), Number::new(1, 10));
+    /// ```
+    pub fn from_f64(value: f64) -> Option<Number> {
+        // convergents of the continued fraction of the value, the first one equal to the value is the simplest
+        let (mut previous_numerator, mut numerator) = (0i128, 1i128);
+        let (mut previous_denominator, mut denominator) = (1i128, 0i128);
+        let mut x = value;
+
+        loop {
+            let integer = x.floor();
+
+            if !integer.is_finite() || integer.abs() > i64::MAX as f64 {
+                return None;
+            }
+
+            let term = integer as i128;
+
+            (previous_numerator, numerator) = (numerator, term * numerator + previous_numerator);
+            (previous_denominator, denominator) = (denominator, term * denominator + previous_denominator);
+
+            let convergent = Number::from_i128(numerator, denominator)?;
+
+            if convergent.as_f64() == value {
+                return Some(convergent);
+            }
+
+            x = 1.0 / (x - integer);
+        }
+    }
+
+    /// Returns the number of decimal places needed to write the number exactly,
+    /// [None] if the number has no finite decimal representation (e.g. 1/3).
+    fn decimal_places(&self) -> Option<u32> {
+        let mut denominator = self.denominator;
+        let mut twos = 0;
+        let mut fives = 0;
+
+        while denominator % 2 == 0 {
+            denominator /= 2;
+            twos += 1;
+        }
+
+        while denominator % 5 == 0 {
+            denominator /= 5;
+            fives += 1;
+        }
+
+        (denominator == 1).then_some(twos.max(fives))
+    }
+}
+
+impl From<i64> for Number {
+    fn from(value: i64) -> Self {
+        Number { numerator: value, denominator: 1 }
+    }
+}
+
+impl FromStr for Number {
+    type Err = Error;
+
+    /// Parses an integer (e.g. `-05`), a decimal number (e.g. `10.25`) or a fraction (e.g. `1/3`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((numerator, denominator)) = s.split_once('/') {
+            return Number::new(numerator.parse()?, denominator.parse()?).ok_or_else(|| anyhow!("Invalid number, given {}", s));
+        }
+
+        match s.split_once('.') {
+            Some((integer, fraction)) => {
+                if fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(anyhow!("Invalid number, given {}", s));
+                }
+
+                let denominator = 10i64.checked_pow(fraction.len() as u32)
+                    .ok_or_else(|| anyhow!("Invalid number, given {}", s))?;
+                let numerator: i64 = format!("{}{}", integer, fraction).parse()?;
+
+                Number::new(numerator, denominator).ok_or_else(|| anyhow!("Invalid number, given {}", s))
+            },
+            None => Ok(Number::from(s.parse::<i64>()?)),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.decimal_places() {
+            Some(0) => write!(f, "{}", self.numerator),
+            Some(places) => match 10i128.checked_pow(places).and_then(|scale| Some((scale, (self.numerator as i128).checked_mul(scale)?))) {
+                Some((scale, scaled)) => {
+                    let scaled = scaled / self.denominator as i128;
+                    let sign = if scaled < 0 { "-" } else { "" };
+
+                    write!(f, "{}{}.{:0width$}", sign, scaled.abs() / scale, scaled.abs() % scale, width = places as usize)
+                },
+                None => write!(f, "{}/{}", self.numerator, self.denominator),
+            },
+            None => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator as i128 * other.denominator as i128).cmp(&(other.numerator as i128 * self.denominator as i128))
+    }
+}
+
+impl Neg for Number {
+    type Output = Number;
+
+    fn neg(self) -> Self::Output {
+        Number { numerator: self.numerator.saturating_neg(), denominator: self.denominator }
+    }
+}
+
+impl Add for Number {
+    type Output = Number;
+
+    fn add(self, rhs: Number) -> Self::Output {
+        let (numerator, denominator) = self.add_i128(rhs);
+
+        Number::approximate_i128(numerator, denominator)
+    }
+}
+
+impl Add<i64> for Number {
+    type Output = Number;
+
+    fn add(self, rhs: i64) -> Self::Output {
+        self + Number::from(rhs)
+    }
+}
+
+impl Mul for Number {
+    type Output = Number;
+
+    fn mul(self, rhs: Number) -> Self::Output {
+        let (numerator, denominator) = self.mul_i128(rhs);
+
+        Number::approximate_i128(numerator, denominator)
+    }
+}
+
+impl Div for Number {
+    type Output = Number;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Number) -> Self::Output {
+        assert!(rhs.numerator != 0, "Division by zero");
+
+        let (numerator, denominator) = self.div_i128(rhs);
+
+        Number::approximate_i128(numerator, denominator)
+    }
+}
+
+impl Mul<i64> for Number {
+    type Output = Number;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        self * Number::from(rhs)
+    }
+}
+
+impl Div<i64> for Number {
+    type Output = Number;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: i64) -> Self::Output {
+        self / Number::from(rhs)
+    }
+}
+
+impl Serialize for Number {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // the shortest representation of the nearest f64 is the exact decimal number if such exists
+        serializer.serialize_f64(self.as_f64())
+    }
+}
+
+impl<'de> Deserialize<'de> for Number {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = f64::deserialize(deserializer)?;
+
+        Number::from_f64(value).ok_or_else(|| de::Error::custom(format!("Invalid number, given {}", value)))
+    }
+}
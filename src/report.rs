@@ -0,0 +1,91 @@
+//! Module for the common abstraction of decoded weather reports.
+
+use serde::Serialize;
+
+use crate::metar::{CloudLayer, Metar, MetarTime, Pressure, Temperature, Visibility, Wind};
+
+/// Kind of a weather report.
+///
+/// JSON representation is in lowercase snake case.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportKind {
+    /// METAR or SPECI report.
+    Metar,
+}
+
+/// Weather conditions observed at the station at the time of the report.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CurrentConditions<'a> {
+    /// Surface wind groups.
+    ///
+    /// JSON representation is flattened once.
+    #[serde(flatten)]
+    pub wind: &'a Wind,
+    /// Visibility groups.
+    ///
+    /// JSON representation is flattened once.
+    #[serde(flatten)]
+    pub visibility: &'a Visibility,
+    pub clouds: &'a [CloudLayer],
+    /// Temperature groups.
+    ///
+    /// JSON representation is flattened once.
+    #[serde(flatten)]
+    pub temperature: &'a Temperature,
+    /// Pressure group.
+    ///
+    /// JSON representation is flattened once.
+    #[serde(flatten)]
+    pub pressure: &'a Pressure,
+}
+
+/// Decoded weather report of any kind.
+///
+/// Allows generic storage, filtering and rendering code to work over all decoded reports.
+pub trait WeatherReport {
+    /// ICAO airport code.
+    fn station_id(&self) -> Option<&str>;
+
+    /// Observation time of the report.
+    fn observation_time(&self) -> Option<MetarTime>;
+
+    /// Text of the report as decoded.
+    fn raw_text(&self) -> &str;
+
+    /// Kind of the report, see [ReportKind].
+    fn kind(&self) -> ReportKind;
+
+    /// Weather conditions observed at the station at the time of the report.
+    fn current_conditions(&self) -> CurrentConditions<'_>;
+}
+
+impl WeatherReport for Metar {
+    fn station_id(&self) -> Option<&str> {
+        self.header.station_id.as_deref()
+    }
+
+    fn observation_time(&self) -> Option<MetarTime> {
+        self.header.observation_time
+    }
+
+    fn raw_text(&self) -> &str {
+        &self.report
+    }
+
+    fn kind(&self) -> ReportKind {
+        ReportKind::Metar
+    }
+
+    fn current_conditions(&self) -> CurrentConditions<'_> {
+        CurrentConditions {
+            wind: &self.wind,
+            visibility: &self.visibility,
+            clouds: &self.clouds,
+            temperature: &self.temperature,
+            pressure: &self.pressure,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Integration tests for the anchor policies of the datetime conversion.
+
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use rweather_decoder::metar::{decode_metar_with_options, AnchorPolicy, DecodeOptions, MetarTime};
+use serde_json::json;
+
+fn date_time(s: &str) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+}
+
+fn day_time(day: u32, hour: u32, minute: u32) -> MetarTime {
+    serde_json::from_value(json!({"value_type": "day_time", "value": [day, format!("{:02}:{:02}:00Z", hour, minute)]})).unwrap()
+}
+
+fn expected(s: &str) -> Option<MetarTime> {
+    serde_json::from_value(json!({"value_type": "date_time", "value": format!("{}:00Z", s.replace(' ', "T"))})).unwrap()
+}
+
+#[test]
+fn it_anchor_policy() -> Result<()> {
+    let anchor_time = date_time("2024-02-01 00:10");
+
+    // day 31 near February resolves to January, day 30 of February does not exist
+    assert_eq!(day_time(31, 23, 50).to_date_time(anchor_time, AnchorPolicy::Reject), expected("2024-01-31 23:50"));
+    assert_eq!(day_time(30, 12, 0).to_date_time(date_time("2024-02-20 00:00"), AnchorPolicy::Reject), expected("2024-01-30 12:00"));
+    assert_eq!(day_time(0, 12, 0).to_date_time(anchor_time, AnchorPolicy::Reject), None);
+    assert_eq!(day_time(0, 12, 0).to_date_time(anchor_time, AnchorPolicy::ClampToMonthEnd), None);
+
+    // nearest is in the future, past-only keeps the previous month
+    let anchor_time = date_time("2024-02-29 23:00");
+    assert_eq!(day_time(1, 1, 0).to_date_time(anchor_time, AnchorPolicy::Reject), expected("2024-03-01 01:00"));
+    assert_eq!(day_time(1, 1, 0).to_date_time(anchor_time, AnchorPolicy::PastOnly), expected("2024-02-01 01:00"));
+
+    let anchor_time = date_time("2024-03-05 00:00");
+    assert_eq!(day_time(31, 12, 0).to_date_time(anchor_time, AnchorPolicy::PastOnly), expected("2024-01-31 12:00"));
+    assert_eq!(day_time(31, 12, 0).to_date_time(date_time("2024-02-25 00:00"), AnchorPolicy::Reject), expected("2024-01-31 12:00"));
+    assert_eq!(day_time(31, 12, 0).to_date_time(date_time("2024-02-25 00:00"), AnchorPolicy::ClampToMonthEnd), expected("2024-01-31 12:00"));
+
+    // the day is clamped only if no nearby month contains it
+    assert_eq!(day_time(35, 12, 0).to_date_time(date_time("2024-02-25 00:00"), AnchorPolicy::Reject), None);
+    assert_eq!(day_time(35, 12, 0).to_date_time(date_time("2024-02-25 00:00"), AnchorPolicy::ClampToMonthEnd), expected("2024-02-29 12:00"));
+
+    let options = DecodeOptions::new()
+        .anchor_time(date_time("2024-02-29 15:55"))
+        .anchor_policy(AnchorPolicy::PastOnly);
+
+    let metar = decode_metar_with_options("LKPR 291600Z 27010KT CAVOK 24/14 Q1015", &options)?;
+    assert_eq!(metar.header.observation_time, expected("2024-01-29 16:00"));
+
+    Ok(())
+}
+
+#[test]
+fn it_anchor_policy_remark_times() -> Result<()> {
+    let metar = decode_metar_with_options("KXYZ 120010Z 27010KT 10SM FEW030 24/14 A2992 RMK PK WND 27045/2350", &DecodeOptions::new())?;
+    assert_eq!(metar.remarks.peak_wind.unwrap().time, Some(day_time(11, 23, 50)));
+
+    // the day before the 1st depends on the month
+    let metar = decode_metar_with_options("KXYZ 010010Z 27010KT 10SM FEW030 24/14 A2992 RMK PK WND 27045/2350", &DecodeOptions::new())?;
+    let time: MetarTime = serde_json::from_value(json!({"value_type": "time", "value": "23:50:00Z"}))?;
+    assert_eq!(metar.remarks.peak_wind.unwrap().time, Some(time));
+
+    let report = "KXYZ 122350Z 27010KT 10SM FEW030 24/14 A2992 RMK PK WND 27045/0005";
+
+    let metar = decode_metar_with_options(report, &DecodeOptions::new())?;
+    assert_eq!(metar.remarks.peak_wind.unwrap().time, Some(day_time(13, 0, 5)));
+
+    let metar = decode_metar_with_options(report, &DecodeOptions::new().anchor_policy(AnchorPolicy::PastOnly))?;
+    assert_eq!(metar.remarks.peak_wind.unwrap().time, Some(day_time(12, 0, 5)));
+
+    let options = DecodeOptions::new()
+        .anchor_time(date_time("2024-02-12 23:55"))
+        .anchor_policy(AnchorPolicy::PastOnly);
+
+    let metar = decode_metar_with_options(report, &options)?;
+    assert_eq!(metar.remarks.peak_wind.unwrap().time, expected("2024-02-12 00:05"));
+
+    Ok(())
+}
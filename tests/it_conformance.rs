@@ -0,0 +1,96 @@
+//! Integration tests for the strict conformance mode.
+
+use anyhow::Result;
+use rweather_decoder::metar::{
+    conformance::ViolationKind, decode_metar, decode_metar_with_options, diagnostics::GroupKind, DecodeOptions,
+};
+
+#[test]
+fn it_conformance_conforming() -> Result<()> {
+    let report = "LKPR 121600Z 27010G25KT 240V300 4500 -RA BR FEW030 BKN050 14/08 Q1013 NOSIG";
+    let metar = decode_metar_with_options(report, &DecodeOptions::new().strict(true))?;
+
+    assert_eq!(metar.violations, Some(Vec::new()));
+    assert_eq!(metar.provenance, None);
+
+    let metar = decode_metar(report, None)?;
+
+    assert_eq!(metar.violations, None);
+    assert!(serde_json::to_value(&metar)?.get("violations").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn it_conformance_violations() -> Result<()> {
+    let report = "LKPR 121600Z 27510KT 1234 FEW105 Q1013 SKC XYZ BECMG 27510KT 0550";
+    let metar = decode_metar_with_options(report, &DecodeOptions::new().strict(true))?;
+
+    let violations: Vec<_> = metar.violations.as_ref().unwrap().iter()
+        .map(|v| (v.kind, v.group, v.span.clone().map(|span| &metar.report[span])))
+        .collect();
+
+    assert_eq!(violations, vec![
+        (ViolationKind::GroupOrder, Some(GroupKind::Cloud), Some("SKC")),
+        (ViolationKind::UnrecognizedGroup, None, Some("XYZ")),
+        (ViolationKind::MissingGroup, Some(GroupKind::Temperature), None),
+        (ViolationKind::WindDirectionStep, Some(GroupKind::Wind), Some("27510KT")),
+        (ViolationKind::VisibilityStep, Some(GroupKind::Visibility), Some("1234")),
+        (ViolationKind::CloudHeightStep, Some(GroupKind::Cloud), Some("FEW105")),
+        (ViolationKind::WindDirectionStep, Some(GroupKind::Wind), Some("27510KT")),
+        (ViolationKind::CodeCombination, Some(GroupKind::Cloud), Some("SKC")),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn it_conformance_cavok() -> Result<()> {
+    let report = "LKPR 121600Z 27010KT CAVOK R24/1200 -RA 14/08 Q1013 RMK XYZ";
+    let metar = decode_metar_with_options(report, &DecodeOptions::new().strict(true).provenance(true))?;
+
+    let violations: Vec<_> = metar.violations.as_ref().unwrap().iter()
+        .map(|v| (v.kind, v.group, v.span.clone().map(|span| &metar.report[span])))
+        .collect();
+
+    assert_eq!(violations, vec![
+        (ViolationKind::CodeCombination, Some(GroupKind::RunwayVisualRange), Some("R24/1200")),
+        (ViolationKind::CodeCombination, Some(GroupKind::PresentWeather), Some("-RA")),
+    ]);
+    assert!(metar.provenance.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn it_conformance_nil_clouds() -> Result<()> {
+    let report = "LKPR 121620Z AUTO 27010KT 9999 ////// 14/08 Q1013";
+    let metar = decode_metar_with_options(report, &DecodeOptions::new().strict(true))?;
+
+    let violations: Vec<_> = metar.violations.as_ref().unwrap().iter()
+        .map(|v| (v.kind, v.group, v.span.clone().map(|span| &metar.report[span])))
+        .collect();
+
+    assert_eq!(metar.clouds.len(), 1);
+    assert_eq!(violations, vec![(ViolationKind::MissingGroup, Some(GroupKind::Cloud), Some("//////"))]);
+
+    Ok(())
+}
+
+#[test]
+fn it_conformance_trend() -> Result<()> {
+    let report = "LKPR 121600Z 27010KT 9999 FEW030 14/08 Q1013 BECMG FM1630 27015KT 275V335 -RA 4500 BKN015 TEMPO 3000 1250NE";
+    let metar = decode_metar_with_options(report, &DecodeOptions::new().strict(true))?;
+
+    let violations: Vec<_> = metar.violations.as_ref().unwrap().iter()
+        .map(|v| (v.kind, v.group, v.span.clone().map(|span| &metar.report[span])))
+        .collect();
+
+    assert_eq!(violations, vec![
+        (ViolationKind::GroupOrder, Some(GroupKind::Visibility), Some("4500")),
+        (ViolationKind::WindDirectionStep, Some(GroupKind::Wind), Some("27015KT 275V335")),
+        (ViolationKind::VisibilityStep, Some(GroupKind::Visibility), Some("3000 1250NE")),
+    ]);
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+//! Integration tests for the errors of the METAR decoding.
+
+use rweather_decoder::metar::{decode_metar, decode_metar_with_options, error::DecodeError, DecodeOptions, Runway};
+
+#[test]
+fn it_decode_error() {
+    let options = DecodeOptions::new().reject_invalid(true);
+
+    let error = decode_metar_with_options("lkpr  122575z 27010KT 9999 FEW030 24/14 Q1015 NOSIG=", &options).unwrap_err();
+
+    assert!(matches!(error, DecodeError::Header(_)));
+    assert_eq!(error.token(), "LKPR 122575Z");
+    assert_eq!(error.span(), 0..12);
+
+    let metar = decode_metar("KXYZ 121600Z 27010KT 9999 R45/0600N FEW030 24/14 A2992", None).unwrap();
+
+    assert!(matches!(&metar.runway_visual_ranges[0].runway, Runway::Other { designator } if designator == "45"));
+
+    let error = decode_metar_with_options("LKPR 121600Z 27010KT CAVOK 24/14 Q1015 BECMG FM1675 BKN015", &options).unwrap_err();
+
+    assert!(matches!(error, DecodeError::TrendTime(_)));
+    assert_eq!(error.token(), "FM1675");
+    assert_eq!(error.span(), 45..51);
+    assert_eq!(error.to_string(), "Invalid trend time group, given FM1675 at 45..51");
+}
+
+#[test]
+fn it_decode_error_recovered() {
+    let metar = decode_metar("lkpr  122575z 27010KT 9999 FEW030 24/14 Q1015 BECMG FM1675 BKN015=", None).unwrap();
+
+    assert_eq!(metar.header.station_id.as_deref(), Some("LKPR"));
+    assert_eq!(metar.header.observation_time, None);
+    assert!(metar.wind.wind_speed.is_some());
+    assert_eq!(metar.trend_changes[0].clouds.len(), 1);
+
+    let invalid_groups: Vec<_> = metar.diagnostics.invalid_groups.iter()
+        .map(|e| (e.group(), &metar.report[e.span()]))
+        .collect();
+
+    assert_eq!(invalid_groups, vec![("header", "LKPR 122575Z"), ("trend time", "FM1675")]);
+    assert!(!metar.diagnostics.is_clean());
+}
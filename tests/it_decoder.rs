@@ -0,0 +1,153 @@
+//! Integration tests for the decoder and its options.
+
+use anyhow::Result;
+use rweather_decoder::metar::{
+    decode_metar, remark::{Remark, RemarkDecoder, RemarkRegion}, units::UnitSystem, DecodeOptions,
+    Decoder, Metar, Unit,
+};
+
+struct FlagDecoder;
+
+impl RemarkDecoder for FlagDecoder {
+    type Item = bool;
+
+    fn name(&self) -> &str {
+        "flag"
+    }
+
+    fn decode(&self, text: &str) -> Option<(Self::Item, usize)> {
+        text.starts_with("FLAG ").then_some((true, 5))
+    }
+}
+
+#[test]
+fn it_decoder_remark_decoders() -> Result<()> {
+    let report = "LKPR 121600Z 27010KT 9999 FEW030 14/08 Q1013 RMK FLAG";
+    let decoder = Decoder::new(DecodeOptions::new()).remark_decoder(FlagDecoder);
+
+    let metar = decoder.decode(report)?;
+
+    assert!(matches!(&metar.remarks.items[..], [Remark::Custom { name, .. }] if name == "flag"));
+
+    // other decoders are not affected
+    let metar = decode_metar(report, None)?;
+
+    assert!(metar.remarks.items.is_empty());
+    assert_eq!(metar.remarks.unparsed, vec!["FLAG"]);
+    assert_eq!(metar, Decoder::new(DecodeOptions::new()).decode(report)?);
+
+    Ok(())
+}
+
+#[test]
+fn it_decoder_region_and_remarks() -> Result<()> {
+    let report = "EDDF 130700Z 24010KT 9999 FEW030 15/08 Q1013 RMK QBB600 AO2";
+
+    let metar = decode_metar(report, None)?;
+
+    assert_eq!(metar.remarks.cloud_base, None);
+    assert_eq!(metar.remarks.unparsed, vec!["QBB600", "AO2"]);
+
+    let decoder = Decoder::new(DecodeOptions::new().region(RemarkRegion::Russia));
+    let metar = decoder.decode(report)?;
+
+    assert_eq!(metar.remarks.cloud_base.map(|q| q.value.as_f64()), Some(Some(600.0)));
+    assert_eq!(metar.remarks.unparsed, vec!["AO2"]);
+
+    let decoder = Decoder::new(DecodeOptions::new().remarks(false));
+    let metar = decoder.decode(report)?;
+
+    assert_eq!(metar.remarks.raw.as_deref(), Some("QBB600 AO2"));
+    assert_eq!(metar.remarks.automated_station_type, None);
+    assert!(metar.remarks.unparsed.is_empty());
+    assert!(!decoder.options().remarks);
+
+    Ok(())
+}
+
+#[test]
+fn it_decoder_fmh1_region() -> Result<()> {
+    let report = "EGUL 121650Z 24010KT 9999 FEW030 15/08 Q1013 RMK AO2 SLP132 60010 CB DSNT N";
+
+    let metar = decode_metar(report, None)?;
+
+    assert_eq!(metar.remarks.automated_station_type, None);
+    assert_eq!(metar.remarks.three_or_six_hour_precipitation, None);
+    assert_eq!(metar.remarks.convective_activities.len(), 1);
+    assert_eq!(metar.remarks.unparsed, vec!["AO2", "SLP132", "60010"]);
+
+    let decoder = Decoder::new(DecodeOptions::new().region(RemarkRegion::UnitedStates));
+    let metar = decoder.decode(report)?;
+
+    assert!(metar.remarks.automated_station_type.is_some());
+    assert!(metar.remarks.sea_level_pressure.is_some());
+    assert!(metar.remarks.three_or_six_hour_precipitation.is_some());
+    assert!(metar.remarks.unparsed.is_empty());
+
+    let metar = decode_metar("CWDQ 121600Z AUTO 27010KT 9999 FEW030 08/02 A2992 RMK AO1 SLP175 T00800020 51006 60010", None)?;
+
+    assert!(metar.remarks.precise_temperature.is_some());
+    assert!(metar.remarks.pressure_tendency.is_some());
+    assert_eq!(metar.remarks.unparsed, vec!["60010"]);
+
+    Ok(())
+}
+
+#[test]
+fn it_decoder_units() -> Result<()> {
+    let decoder = Decoder::new(DecodeOptions::new().units(UnitSystem::Metric));
+    let metar = decoder.decode("KXYZ 121600Z 27010KT 3SM R27/2400FT FEW030 24/14 A2992 RMK SLP132 P0010 4/012")?;
+
+    let wind_speed = metar.wind.wind_speed.unwrap();
+    assert_eq!((wind_speed.value.as_f64(), wind_speed.units), (Some(10.0 * 1852.0 / 3600.0), Unit::MetrePerSecond));
+
+    let visibility = metar.visibility.prevailing_visibility.unwrap();
+    assert_eq!((visibility.value.as_f64(), visibility.units), (Some(4828.032), Unit::Metre));
+
+    let visual_range = metar.runway_visual_ranges[0].visual_range;
+    assert_eq!((visual_range.value.as_f64(), visual_range.units), (Some(731.52), Unit::Metre));
+
+    let height = metar.clouds[0].height.unwrap();
+    assert_eq!((height.value.as_f64(), height.units, height.resolution.map(|r| r.as_f64())), (Some(914.4), Unit::Metre, Some(30.48)));
+
+    let pressure = metar.pressure.pressure.unwrap();
+    assert_eq!((pressure.value.as_f64(), pressure.units), (Some(1013.2075888), Unit::HectoPascal));
+
+    let precipitation = metar.remarks.hourly_precipitation.unwrap().amount.unwrap();
+    assert_eq!((precipitation.value.as_f64(), precipitation.units), (Some(2.54), Unit::Millimetre));
+
+    let snow_depth = metar.remarks.snow_depth.unwrap();
+    assert_eq!((snow_depth.value.as_f64(), snow_depth.units), (Some(30.48), Unit::Centimetre));
+
+    let temperature = metar.temperature.temperature.unwrap();
+    assert_eq!((temperature.value.as_f64(), temperature.units), (Some(24.0), Unit::DegreeCelsius));
+
+    let decoder = Decoder::new(DecodeOptions::new().units(UnitSystem::UnitedStates));
+    let metar = decoder.decode("LKPR 121600Z 27005MPS 1500 FEW030 14/08 Q1013")?;
+
+    assert_eq!(metar.wind.wind_speed.map(|q| q.units), Some(Unit::Knot));
+    assert_eq!(metar.visibility.prevailing_visibility.map(|q| q.units), Some(Unit::StatuteMile));
+    assert_eq!(metar.clouds[0].height.and_then(|q| q.value.as_f64()), Some(3000.0));
+    assert_eq!(metar.pressure.pressure.map(|q| q.units), Some(Unit::InchOfMercury));
+
+    Ok(())
+}
+
+#[test]
+fn it_decoder_units_json() -> Result<()> {
+    let decoder = Decoder::new(DecodeOptions::new().units(UnitSystem::Metric));
+    let metar = decoder.decode("KXYZ 121600Z 24010KT 10SM FEW020 24/14 A2992")?;
+    let value = serde_json::to_value(&metar)?;
+
+    // converted values without an exact decimal form are still JSON numbers
+    assert!(value["wind_speed"]["value"].is_f64());
+    assert!(value["wind_speed"]["resolution"].is_f64());
+    assert!(value["clouds"][0]["height"]["value"].is_f64());
+    assert_eq!(value["wind_speed"]["units"], "m/s");
+
+    let restored: Metar = serde_json::from_value(value)?;
+
+    assert_eq!(restored.wind.wind_speed, metar.wind.wind_speed);
+
+    Ok(())
+}
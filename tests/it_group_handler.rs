@@ -0,0 +1,53 @@
+//! Integration tests for the custom group handlers.
+
+use anyhow::Result;
+use rweather_decoder::metar::{extension::{GroupHandler, GroupSection, HandlerPlacement}, DecodeOptions, Decoder};
+use serde_json::json;
+
+/// Handles groups consisting of the given prefix and digits, e.g. `AWOS3`.
+struct PrefixHandler {
+    name: &'static str,
+    prefix: &'static str,
+}
+
+impl GroupHandler for PrefixHandler {
+    type Item = String;
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn handle(&self, text: &str) -> Option<(Self::Item, usize)> {
+        let rest = text.strip_prefix(self.prefix)?;
+        let digits = rest.find(' ')?;
+
+        rest[..digits].chars().all(|c| c.is_ascii_digit())
+            .then(|| (rest[..digits].to_string(), self.prefix.len() + digits + 1))
+    }
+}
+
+#[test]
+fn it_group_handler() -> Result<()> {
+    let decoder = Decoder::new(DecodeOptions::new())
+        .group_handler(GroupSection::Main, HandlerPlacement::AfterBuiltIn, 0, PrefixHandler { name: "awos", prefix: "AWOS" })
+        .group_handler(GroupSection::Main, HandlerPlacement::BeforeBuiltIn, 0, PrefixHandler { name: "visibility_override", prefix: "" })
+        .group_handler(GroupSection::Main, HandlerPlacement::BeforeBuiltIn, 1, PrefixHandler { name: "high_priority_awos", prefix: "AWOS" })
+        .group_handler(GroupSection::Trend, HandlerPlacement::AfterBuiltIn, 0, PrefixHandler { name: "trend_code", prefix: "X" });
+
+    let metar = decoder.decode("KXYZ 121600Z 27010KT 9999 AWOS3 FEW030 24/14 A2992 TEMPO X42 SHRA")?;
+
+    assert_eq!(metar.visibility.prevailing_visibility, None);
+    assert_eq!(metar.clouds.len(), 1);
+    assert_eq!(metar.trend_changes.len(), 1);
+    assert_eq!(metar.trend_changes[0].weather.len(), 1);
+
+    let value = serde_json::to_value(&metar)?;
+
+    assert_eq!(value["extensions"], json!({
+        "visibility_override": ["9999"],
+        "high_priority_awos": ["3"],
+        "trend_code": ["42"],
+    }));
+
+    Ok(())
+}
@@ -1,16 +1,16 @@
-/// Integration tests for METAR.
+//! Integration tests for METAR.
 
-use std::{path::PathBuf, process::Command, fs::File, io::BufReader};
+use std::{path::{Path, PathBuf}, process::Command, fs::File, io::BufReader};
 
 use anyhow::Result;
 use rweather_decoder::metar::Metar;
 use tempfile::NamedTempFile;
 
-fn run_decode_metar(input: &PathBuf, output: &PathBuf, file_format: &str) -> Result<()> {
+fn run_decode_metar(input: &Path, output: &Path, file_format: &str) -> Result<()> {
     let binary_path = env!("CARGO_BIN_EXE_decode-metar");
 
-    let status = Command::new(&binary_path)
-        .args(&[
+    let status = Command::new(binary_path)
+        .args([
             input.as_os_str().to_str().unwrap(),
             output.as_os_str().to_str().unwrap(),
             "--quiet",
@@ -123,3 +123,58 @@ fn it_metar_trend() -> Result<()> {
 fn it_metar_null() -> Result<()> {
     it_metar_template("it_null_input.txt", "it_null_output.json", "noaa-metar-cycles")
 }
+
+#[test]
+fn it_metar_remark() -> Result<()> {
+    it_metar_template("it_remark_input.txt", "it_remark_output.json", "noaa-metar-cycles")
+}
+
+#[test]
+fn it_metar_remark_precipitation() -> Result<()> {
+    it_metar_template("it_remark_precipitation_input.txt", "it_remark_precipitation_output.json", "noaa-metar-cycles")
+}
+
+#[test]
+fn it_metar_remark_temperature_pressure() -> Result<()> {
+    it_metar_template("it_remark_temperature_pressure_input.txt", "it_remark_temperature_pressure_output.json", "noaa-metar-cycles")
+}
+
+#[test]
+fn it_metar_remark_wind() -> Result<()> {
+    it_metar_template("it_remark_wind_input.txt", "it_remark_wind_output.json", "noaa-metar-cycles")
+}
+
+#[test]
+fn it_metar_remark_weather_events() -> Result<()> {
+    it_metar_template("it_remark_weather_events_input.txt", "it_remark_weather_events_output.json", "noaa-metar-cycles")
+}
+
+#[test]
+fn it_metar_remark_convection() -> Result<()> {
+    it_metar_template("it_remark_convection_input.txt", "it_remark_convection_output.json", "noaa-metar-cycles")
+}
+
+#[test]
+fn it_metar_remark_visibility_ceiling() -> Result<()> {
+    it_metar_template("it_remark_visibility_ceiling_input.txt", "it_remark_visibility_ceiling_output.json", "noaa-metar-cycles")
+}
+
+#[test]
+fn it_metar_remark_sensor() -> Result<()> {
+    it_metar_template("it_remark_sensor_input.txt", "it_remark_sensor_output.json", "noaa-metar-cycles")
+}
+
+#[test]
+fn it_metar_remark_cloud_opacity() -> Result<()> {
+    it_metar_template("it_remark_cloud_opacity_input.txt", "it_remark_cloud_opacity_output.json", "noaa-metar-cycles")
+}
+
+#[test]
+fn it_metar_remark_national() -> Result<()> {
+    it_metar_template("it_remark_national_input.txt", "it_remark_national_output.json", "noaa-metar-cycles")
+}
+
+#[test]
+fn it_metar_diagnostics() -> Result<()> {
+    it_metar_template("it_diagnostics_input.txt", "it_diagnostics_output.json", "noaa-metar-cycles")
+}
@@ -4,7 +4,7 @@ APP=target/release/decode-metar
 IN_OUT_PATH=tests/data/metar
 
 plain_style_groups=("daytime")
-cycles_style_groups=("anchor_time" "cloud" "header" "present_weather" "pressure" "recent_weather" "rvr" "sea" "temperature" "trend" "visibility" "wind" "wind_shear" "null")
+cycles_style_groups=("anchor_time" "cloud" "header" "present_weather" "pressure" "recent_weather" "rvr" "sea" "temperature" "trend" "visibility" "wind" "wind_shear" "null" "remark" "remark_precipitation" "remark_temperature_pressure" "remark_wind" "remark_weather_events" "remark_convection" "remark_visibility_ceiling" "remark_sensor" "remark_cloud_opacity" "remark_national" "diagnostics")
 
 for group in ${plain_style_groups[@]}; do
     ${APP} -f plain -p ${IN_OUT_PATH}/it_${group}_input.txt ${IN_OUT_PATH}/it_${group}_output.json
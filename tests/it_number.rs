@@ -0,0 +1,41 @@
+//! Integration tests for the exact numbers.
+
+use anyhow::Result;
+use rweather_decoder::number::Number;
+
+#[test]
+fn it_number_serde() -> Result<()> {
+    let third = Number::new(1, 3).unwrap();
+
+    assert_eq!(serde_json::to_string(&third)?, "0.3333333333333333");
+    assert_eq!(serde_json::from_str::<Number>("0.3333333333333333")?, third);
+
+    let speed = Number::new(463, 90).unwrap();
+
+    assert!(serde_json::to_value(speed)?.is_f64());
+    assert_eq!(serde_json::from_value::<Number>(serde_json::to_value(speed)?)?, speed);
+
+    let value = Number::new(7, 4).unwrap();
+
+    assert_eq!(serde_json::to_string(&value)?, "1.75");
+    assert_eq!(serde_json::from_str::<Number>("1.75")?, value);
+    assert!(serde_json::from_str::<Number>("\"1.75\"").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn it_number_overflow() {
+    let large = Number::from(i64::MAX);
+    let small = Number::new(1, i64::MAX).unwrap();
+
+    assert_eq!(large.checked_add(Number::from(1)), None);
+    assert_eq!(large.checked_mul(Number::from(2)), None);
+    assert_eq!(small.checked_div(large), None);
+    assert_eq!(large.checked_div(Number::from(0)), None);
+
+    assert_eq!(large + 1, large);
+    assert_eq!(-large * 2, Number::from(i64::MIN));
+    assert_eq!(small / large, Number::from(0));
+    assert_eq!(Number::new(2, 3).unwrap() * Number::new(3, 2).unwrap(), Number::from(1));
+}
@@ -0,0 +1,35 @@
+//! Integration tests for the provenance of decoded elements.
+
+use anyhow::Result;
+use rweather_decoder::metar::{decode_metar, decode_metar_with_options, DecodeOptions};
+
+#[test]
+fn it_provenance() -> Result<()> {
+    let report = "KXYZ 121600Z 27010G20KT 3SM R27/2400FT -RA BR FEW030 BKN050 24/14 A2992 TEMPO 1SM SHRA RMK AO2 PK WND 28030/1530 SLP132";
+    let metar = decode_metar_with_options(report, &DecodeOptions::new().provenance(true))?;
+    let provenance = metar.provenance.as_ref().unwrap();
+
+    let span = |path: &str| provenance.get(path).map(|span| &metar.report[span.clone()]);
+
+    assert_eq!(span("header"), Some("KXYZ 121600Z"));
+    assert_eq!(span("wind"), Some("27010G20KT"));
+    assert_eq!(span("visibility"), Some("3SM"));
+    assert_eq!(span("runway_visual_ranges[0]"), Some("R27/2400FT"));
+    assert_eq!(span("present_weather[1]"), Some("BR"));
+    assert_eq!(span("clouds[1]"), Some("BKN050"));
+    assert_eq!(span("pressure"), Some("A2992"));
+    assert_eq!(span("trend_changes[0].indicator"), Some("TEMPO"));
+    assert_eq!(span("trend_changes[0].visibility"), Some("1SM"));
+    assert_eq!(span("trend_changes[0].weather[0]"), Some("SHRA"));
+    assert_eq!(span("remarks.raw"), Some("AO2 PK WND 28030/1530 SLP132"));
+    assert_eq!(span("remarks.peak_wind"), Some("PK WND 28030/1530"));
+    assert_eq!(span("remarks.sea_level_pressure"), Some("SLP132"));
+    assert_eq!(provenance.len(), 17);
+
+    let metar = decode_metar(report, None)?;
+
+    assert_eq!(metar.provenance, None);
+    assert!(serde_json::to_value(&metar)?.get("provenance").is_none());
+
+    Ok(())
+}
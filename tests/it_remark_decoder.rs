@@ -0,0 +1,74 @@
+//! Integration tests for the custom remark decoders.
+
+use anyhow::Result;
+use rweather_decoder::metar::{
+    decode_metar, decode_metar_with_options, remark::{Remark, RemarkDecoder}, DecodeOptions, Decoder,
+};
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Serialize)]
+struct Altimeter {
+    pressure: f64,
+}
+
+struct AltimeterDecoder;
+
+impl RemarkDecoder for AltimeterDecoder {
+    type Item = Altimeter;
+
+    fn name(&self) -> &str {
+        "altimeter"
+    }
+
+    fn decode(&self, text: &str) -> Option<(Self::Item, usize)> {
+        let group = text.strip_prefix('A')?.get(..5)?;
+
+        if !group[..4].chars().all(|c| c.is_ascii_digit()) || group.as_bytes()[4] != b' ' {
+            return None;
+        }
+
+        let pressure = group[..4].parse::<f64>().ok()? / 100.0;
+
+        Some((Altimeter { pressure }, 6))
+    }
+}
+
+#[test]
+fn it_remark_decoder() -> Result<()> {
+    let decoder = Decoder::new(DecodeOptions::new()).remark_decoder(AltimeterDecoder);
+    let metar = decoder.decode("RJNY 130734Z 05007KT 5000 SHRA BR FEW005 16/15 Q1018 RMK 1ST005 A3008 R1700")?;
+
+    let expected = Remark::Custom { name: "altimeter".to_string(), value: json!({"pressure": 30.08}) };
+
+    assert_eq!(metar.remarks.items, vec![expected]);
+    assert_eq!(metar.remarks.unparsed, vec!["R1700"]);
+
+    let value = serde_json::to_value(&metar)?;
+
+    assert_eq!(value["remarks"]["items"], json!([{"remark_type": "custom", "name": "altimeter", "value": {"pressure": 30.08}}]));
+
+    Ok(())
+}
+
+#[test]
+fn it_remark_decoder_not_shared() -> Result<()> {
+    let report = "RJNY 130734Z 05007KT 5000 SHRA BR FEW005 16/15 Q1018 RMK A3008";
+    let decoder = Decoder::new(DecodeOptions::new()).remark_decoder(AltimeterDecoder);
+
+    assert_eq!(decoder.decode(report)?.remarks.items.len(), 1);
+
+    // decoders of another decoder are never consulted
+    let metars = [
+        decode_metar(report, None)?,
+        decode_metar_with_options(report, decoder.options())?,
+        Decoder::new(DecodeOptions::new()).decode(report)?,
+    ];
+
+    for metar in metars {
+        assert!(metar.remarks.items.is_empty());
+        assert_eq!(metar.remarks.unparsed, vec!["A3008"]);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Integration tests for the common abstraction of weather reports.
+
+use anyhow::Result;
+use rweather_decoder::{metar::decode_metar, report::{ReportKind, WeatherReport}};
+use serde_json::json;
+
+fn describe(report: &dyn WeatherReport) -> Result<serde_json::Value> {
+    let conditions = serde_json::to_value(report.current_conditions())?;
+
+    Ok(json!({
+        "station_id": report.station_id(),
+        "kind": report.kind(),
+        "raw_text": report.raw_text(),
+        "wind_speed": conditions["wind_speed"]["value"],
+        "clouds": conditions["clouds"].as_array().map(|c| c.len()),
+        "temperature": conditions["temperature"]["value"],
+    }))
+}
+
+#[test]
+fn it_report() -> Result<()> {
+    let metar = decode_metar("GCTS 121600Z 07023KT 9999 FEW030 24/14 Q1015 NOSIG", None)?;
+
+    assert_eq!(metar.kind(), ReportKind::Metar);
+    assert_eq!(metar.observation_time(), metar.header.observation_time);
+    assert_eq!(metar.current_conditions().pressure, &metar.pressure);
+
+    assert_eq!(describe(&metar)?, json!({
+        "station_id": "GCTS",
+        "kind": "metar",
+        "raw_text": "GCTS 121600Z 07023KT 9999 FEW030 24/14 Q1015 NOSIG",
+        "wind_speed": 23.0,
+        "clouds": 1,
+        "temperature": 24.0,
+    }));
+
+    Ok(())
+}